@@ -5,11 +5,22 @@ pub fn build(b: *std.Build) void {
     const target = b.standardTargetOptions(.{});
     const optimize = b.standardOptimizeOption(.{});
 
+    // `zig build test-fifo -Dtsan` (or test-chaos, or the unit tests) runs
+    // the realistic-scale stress binaries under ThreadSanitizer — the
+    // million-message complement to eyeballing the atomics
+    const tsan = b.option(bool, "tsan", "Build tests and benchmarks with ThreadSanitizer") orelse false;
+
     // Core channel module
     const channel = b.addModule("channel", .{
         .root_source_file = b.path("src/channel.zig"),
     });
 
+    // Reusable benchmark driver
+    const bench = b.addModule("bench", .{
+        .root_source_file = b.path("src/bench.zig"),
+    });
+    bench.addImport("channel", channel);
+
     // Executables
     const bins = [_]struct { name: []const u8, src: []const u8 }{
         .{ .name = "bench", .src = "src/bench_final.zig" },
@@ -23,10 +34,19 @@ pub fn build(b: *std.Build) void {
             .root_source_file = b.path(bin.src),
             .target = target,
             .optimize = optimize,
+            .sanitize_thread = tsan,
         });
         mod.addImport("channel", channel);
+        mod.addImport("bench", bench);
         const exe = b.addExecutable(.{ .name = bin.name, .root_module = mod });
         b.installArtifact(exe);
+
+        // `zig build bench` / `zig build test-fifo` / ... run directly,
+        // so CI can script benchmark and stress runs without digging in
+        // zig-out
+        const run = b.addRunArtifact(exe);
+        if (b.args) |args| run.addArgs(args);
+        b.step(bin.name, b.fmt("Run {s}", .{bin.name})).dependOn(&run.step);
     }
 
     // Unit tests
@@ -34,6 +54,7 @@ pub fn build(b: *std.Build) void {
         .root_source_file = b.path("src/channel.zig"),
         .target = target,
         .optimize = optimize,
+        .sanitize_thread = tsan,
     });
     const tests = b.addTest(.{ .root_module = test_mod });
     b.step("test", "Run unit tests").dependOn(&b.addRunArtifact(tests).step);
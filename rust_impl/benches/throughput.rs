@@ -0,0 +1,128 @@
+//! Criterion throughput benchmarks, replacing the hand-rolled stats in
+//! the `bench_ab`/`bench_prefetch` binaries for regression tracking:
+//! criterion supplies warmup, confidence intervals, outlier detection
+//! and `--save-baseline`/`--baseline` comparisons.
+//!
+//! Two groups:
+//! - `spsc_pinned`: 1P1C StackRing firehose with both threads pinned,
+//!   at a few batch sizes.
+//! - `prefetch_ab`: the StackRing vs NoPrefetchRing comparison from
+//!   `bench_prefetch`, as two criterion functions in one group so the
+//!   report puts them side by side.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rust_impl::bench_util::pin_to_cpu;
+use rust_impl::no_prefetch_ring::NoPrefetchRing;
+use rust_impl::stack_ring::StackRing;
+use std::thread;
+
+const MSG: u64 = 1_000_000; // messages per iteration (criterion scales reps)
+const RING_SIZE: usize = 1 << 16;
+
+static PREFETCH_RING: StackRing<u32, RING_SIZE> = StackRing::new();
+static NO_PREFETCH_RING: NoPrefetchRing<u32, RING_SIZE> = NoPrefetchRing::new();
+
+/// Push MSG messages through the static StackRing, producer on CPU 0 and
+/// consumer on CPU 1, in batches of `batch`. Returns once both sides join.
+fn spsc_round(batch: usize) {
+    unsafe { PREFETCH_RING.reset() };
+    let ring = &PREFETCH_RING;
+
+    let consumer = thread::spawn(move || {
+        pin_to_cpu(1);
+        let mut count = 0u64;
+        while count < MSG {
+            unsafe {
+                let n = ring.consume_batch(|_| {});
+                if n > 0 {
+                    count += n as u64;
+                } else {
+                    std::hint::spin_loop();
+                }
+            }
+        }
+    });
+
+    pin_to_cpu(0);
+    let mut sent = 0u64;
+    while sent < MSG {
+        let want = (batch as u64).min(MSG - sent) as usize;
+        unsafe {
+            if let Some((ptr, len)) = ring.reserve(want) {
+                for j in 0..len {
+                    *ptr.add(j) = (sent + j as u64) as u32;
+                }
+                ring.commit(len);
+                sent += len as u64;
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+
+    consumer.join().unwrap();
+}
+
+fn no_prefetch_round() {
+    unsafe { NO_PREFETCH_RING.reset() };
+    let ring = &NO_PREFETCH_RING;
+
+    let consumer = thread::spawn(move || {
+        pin_to_cpu(1);
+        let mut count = 0u64;
+        while count < MSG {
+            unsafe {
+                let n = ring.consume_batch(|_| {});
+                if n > 0 {
+                    count += n as u64;
+                } else {
+                    std::hint::spin_loop();
+                }
+            }
+        }
+    });
+
+    pin_to_cpu(0);
+    let mut sent = 0u64;
+    while sent < MSG {
+        unsafe {
+            if let Some((ptr, len)) = ring.reserve(1) {
+                *ptr = sent as u32;
+                ring.commit(len);
+                sent += len as u64;
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+
+    consumer.join().unwrap();
+}
+
+fn bench_spsc_pinned(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spsc_pinned");
+    group.throughput(Throughput::Elements(MSG));
+    group.sample_size(10);
+
+    for batch in [1usize, 256, 32768] {
+        group.bench_with_input(BenchmarkId::from_parameter(batch), &batch, |b, &batch| {
+            b.iter(|| spsc_round(batch));
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_prefetch_ab(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prefetch_ab");
+    group.throughput(Throughput::Elements(MSG));
+    group.sample_size(10);
+
+    group.bench_function("with_prefetch", |b| b.iter(|| spsc_round(1)));
+    group.bench_function("without_prefetch", |b| b.iter(no_prefetch_round));
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_spsc_pinned, bench_prefetch_ab);
+criterion_main!(benches);
@@ -0,0 +1,78 @@
+/* ringmpsc.h — stable ABI for attaching to a ringmpsc ring from C/C++.
+ *
+ * Mirrors rust_impl::ring_header::RingHeader field for field. The Rust
+ * side carries compile-time assertions on every offset below; if you
+ * change one side, change the other and bump RINGMPSC_HEADER_VERSION.
+ *
+ * Requires C11 (<stdatomic.h>). Cursors are free-running 64-bit counters;
+ * a slot index is (cursor & (capacity - 1)), capacity always a power of
+ * two. The producer publishes via `tail` with release ordering, the
+ * consumer frees space via `head` with release ordering; each side reads
+ * the other's cursor with acquire ordering. The data region follows the
+ * header in the same mapping, at an offset agreed out of band.
+ */
+
+#ifndef RINGMPSC_H
+#define RINGMPSC_H
+
+#include <stdatomic.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define RINGMPSC_HEADER_VERSION 1u
+
+/* 384 bytes, 128-byte aligned: one cache-line pair per cursor, then a
+ * cold line for capacity/version/closed. */
+typedef struct ringmpsc_header {
+    /* offset   0 */ _Alignas(128) _Atomic uint64_t head;
+    /* offset   8 */ uint8_t _pad0[120];
+    /* offset 128 */ _Atomic uint64_t tail;
+    /* offset 136 */ uint8_t _pad1[120];
+    /* offset 256 */ uint64_t capacity;
+    /* offset 264 */ uint32_t version;
+    /* offset 268 */ _Atomic uint32_t closed;
+    /* offset 272 */ uint8_t _pad2[112];
+} ringmpsc_header;
+
+_Static_assert(sizeof(ringmpsc_header) == 384, "ringmpsc_header size");
+_Static_assert(_Alignof(ringmpsc_header) == 128, "ringmpsc_header align");
+_Static_assert(offsetof(ringmpsc_header, tail) == 128, "tail offset");
+_Static_assert(offsetof(ringmpsc_header, capacity) == 256, "capacity offset");
+_Static_assert(offsetof(ringmpsc_header, version) == 264, "version offset");
+_Static_assert(offsetof(ringmpsc_header, closed) == 268, "closed offset");
+
+/* Validate a header found in foreign memory before trusting it. */
+static inline bool ringmpsc_header_valid(const ringmpsc_header *h) {
+    return h->version == RINGMPSC_HEADER_VERSION && h->capacity > 0 &&
+           (h->capacity & (h->capacity - 1)) == 0;
+}
+
+/* Items currently in the ring. */
+static inline uint64_t ringmpsc_len(const ringmpsc_header *h) {
+    uint64_t tail = atomic_load_explicit(&h->tail, memory_order_acquire);
+    uint64_t head = atomic_load_explicit(&h->head, memory_order_acquire);
+    return tail - head; /* free-running: wrapping subtraction is correct */
+}
+
+/* Producer side: publish n written items. Single producer only. */
+static inline void ringmpsc_publish(ringmpsc_header *h, uint64_t n) {
+    uint64_t tail = atomic_load_explicit(&h->tail, memory_order_relaxed);
+    atomic_store_explicit(&h->tail, tail + n, memory_order_release);
+}
+
+/* Consumer side: free n consumed items back to the producer. */
+static inline void ringmpsc_advance(ringmpsc_header *h, uint64_t n) {
+    uint64_t head = atomic_load_explicit(&h->head, memory_order_relaxed);
+    atomic_store_explicit(&h->head, head + n, memory_order_release);
+}
+
+static inline bool ringmpsc_is_closed(const ringmpsc_header *h) {
+    return atomic_load_explicit(&h->closed, memory_order_acquire) != 0;
+}
+
+static inline void ringmpsc_close(ringmpsc_header *h) {
+    atomic_store_explicit(&h->closed, 1u, memory_order_release);
+}
+
+#endif /* RINGMPSC_H */
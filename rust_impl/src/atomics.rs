@@ -40,20 +40,128 @@ pub unsafe fn prefetch_write<T>(_ptr: *mut T) {
 
 /// Prefetch multiple cache lines ahead for reading.
 /// Useful for sequential access patterns like ring buffers.
+///
+/// # Safety
+/// `slots_ahead` must be an in-bounds slot index — ring call sites mask
+/// it (`(idx + n) & mask`) *before* calling. The CPU instruction would
+/// tolerate a junk address, but forming a pointer past the allocation
+/// is UB at the pointer-arithmetic level; `wrapping_add` keeps the
+/// arithmetic itself defined so a misuse is at worst a wasted hint
+/// under Miri's pointer model, not instant UB here.
 #[inline(always)]
 pub unsafe fn prefetch_ahead<T>(base: *const T, slots_ahead: usize) {
-    let ptr = base.add(slots_ahead);
+    let ptr = base.wrapping_add(slots_ahead);
     prefetch_read(ptr);
 }
 
 /// Prefetch multiple cache lines ahead for writing with exclusive ownership.
 /// Useful for producer paths in ring buffers.
+///
+/// # Safety
+/// Same contract as [`prefetch_ahead`]: pass a masked in-bounds index.
 #[inline(always)]
 pub unsafe fn prefetch_ahead_write<T>(base: *mut T, slots_ahead: usize) {
-    let ptr = base.add(slots_ahead);
+    let ptr = base.wrapping_add(slots_ahead);
     prefetch_write(ptr);
 }
 
+/// Prefetch every cache line spanned by `count` elements starting at
+/// slot `start_idx`, wrapping at `mask` — warms a whole known batch
+/// region in one call before the batch loop runs, instead of trickling
+/// single-line hints from inside it. Steps one cache line (64 bytes) at
+/// a time, so large elements don't multiply the hint count.
+///
+/// # Safety
+/// `start_idx` must be a masked in-bounds slot index and `mask` the
+/// ring's `capacity - 1`; the wrapped indices then stay in bounds. As
+/// with [`prefetch_ahead`], the address arithmetic uses `wrapping_add`
+/// so a misuse degrades to wasted hints rather than UB here.
+#[inline(always)]
+pub unsafe fn prefetch_range<T>(base: *const T, start_idx: usize, count: usize, mask: usize) {
+    const LINE: usize = 64;
+    let size = std::mem::size_of::<T>();
+    if size == 0 || count == 0 {
+        return;
+    }
+    // Elements per 64-byte line, rounded down to at least one hint per
+    // element for oversized payloads.
+    let step = (LINE / size).max(1);
+    let mut off = 0;
+    while off < count {
+        prefetch_read(base.wrapping_add((start_idx + off) & mask));
+        off += step;
+    }
+}
+
+/// Read the time-stamp counter: a ~nanosecond-overhead timestamp for
+/// per-message latency measurement, where `Instant::now()`'s vDSO call
+/// is already most of the budget. Plain RDTSC can execute out of order
+/// with surrounding instructions — fine for dwell stamps and histogram
+/// sampling; use [`rdtscp`] when the measured code must have retired.
+/// Convert deltas with [`cycles_to_nanos`].
+#[inline(always)]
+#[cfg(target_arch = "x86_64")]
+pub fn rdtsc() -> u64 {
+    // SAFETY: _rdtsc has no preconditions on x86_64.
+    unsafe { core::arch::x86_64::_rdtsc() }
+}
+
+/// [`rdtsc`] with the serializing variant: RDTSCP waits for all prior
+/// instructions to retire before reading the counter, so a stop stamp
+/// can't float above the work it's timing. A few cycles slower.
+#[inline(always)]
+#[cfg(target_arch = "x86_64")]
+pub fn rdtscp() -> u64 {
+    let mut aux = 0u32;
+    // SAFETY: __rdtscp only writes the IA32_TSC_AUX value to `aux`.
+    unsafe { core::arch::x86_64::__rdtscp(&mut aux) }
+}
+
+/// Monotonic-clock fallback on non-x86_64: nanoseconds since first
+/// call, so deltas stay meaningful (`tsc_hz = 1_000_000_000`).
+#[cfg(not(target_arch = "x86_64"))]
+pub fn rdtsc() -> u64 {
+    use std::sync::OnceLock;
+    static EPOCH: OnceLock<std::time::Instant> = OnceLock::new();
+    EPOCH.get_or_init(std::time::Instant::now).elapsed().as_nanos() as u64
+}
+
+/// Same fallback as [`rdtsc`] on non-x86_64.
+#[cfg(not(target_arch = "x86_64"))]
+pub fn rdtscp() -> u64 {
+    rdtsc()
+}
+
+/// Convert a TSC cycle delta to nanoseconds given the counter's
+/// frequency. The TSC is invariant (constant-rate) on anything this
+/// crate targets; calibrate `tsc_hz` once at startup by bracketing a
+/// known `std::thread::sleep` with [`rdtsc`]. Multiplies in u128 so a
+/// large delta times 1e9 can't overflow.
+#[inline(always)]
+pub fn cycles_to_nanos(cycles: u64, tsc_hz: u64) -> u64 {
+    debug_assert!(tsc_hz > 0, "tsc_hz must be a calibrated frequency");
+    ((cycles as u128 * 1_000_000_000) / tsc_hz as u128) as u64
+}
+
+/// Release-store a value into a shared word: everything written before
+/// this call is visible to any thread that [`observe`]s the new value.
+/// The named half of the SPSC publish/observe pattern the ring's
+/// commit/peek pair is built on — use it for ring-adjacent
+/// side-channels (a notification word, a sequence counter) instead of
+/// guessing at orderings.
+#[inline(always)]
+pub fn publish(atomic: &std::sync::atomic::AtomicU64, val: u64) {
+    atomic.store(val, std::sync::atomic::Ordering::Release);
+}
+
+/// Acquire-load the counterpart of [`publish`]: once the published
+/// value is seen, all writes the publisher made before publishing are
+/// visible too.
+#[inline(always)]
+pub fn observe(atomic: &std::sync::atomic::AtomicU64) -> u64 {
+    atomic.load(std::sync::atomic::Ordering::Acquire)
+}
+
 /// Compiler memory barrier hint (stronger than necessary but ensures ordering).
 #[inline(always)]
 pub fn compiler_fence_acquire() {
@@ -70,12 +178,39 @@ pub fn compiler_fence_release() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_publish_observe_roundtrip() {
+        let word = std::sync::atomic::AtomicU64::new(0);
+        publish(&word, 42);
+        assert_eq!(observe(&word), 42);
+    }
+
+    #[test]
+    fn test_rdtsc_monotonic_and_conversion() {
+        let a = rdtsc();
+        let b = rdtscp();
+        let c = rdtsc();
+        // Invariant TSC (and the Instant fallback) never runs backwards
+        assert!(b >= a);
+        assert!(c >= b);
+
+        // 3 GHz: 3000 cycles = 1000 ns
+        assert_eq!(cycles_to_nanos(3_000, 3_000_000_000), 1_000);
+        // No overflow for day-scale deltas
+        assert_eq!(
+            cycles_to_nanos(u64::MAX / 2, 1_000_000_000),
+            u64::MAX / 2
+        );
+    }
+
     #[test]
     fn test_prefetch_compiles() {
         let data: [u64; 4] = [1, 2, 3, 4];
         unsafe {
             prefetch_read(data.as_ptr());
             prefetch_ahead(data.as_ptr(), 2);
+            // Range wrapping across the end of a 4-slot ring
+            prefetch_range(data.as_ptr(), 3, 3, 3);
         }
     }
 }
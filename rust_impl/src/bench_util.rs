@@ -0,0 +1,545 @@
+//! Shared helpers and the reusable benchmark driver for the ad-hoc
+//! benchmark binaries.
+//!
+//! The criterion benches under `benches/` bring their own statistics;
+//! this module exists so the quick-look `bench`/`bench_ab`/
+//! `bench_prefetch` bins don't each carry a hand-rolled copy — and so
+//! external scripts can run the same sweeps programmatically via
+//! [`run`] instead of parsing table output.
+
+use crate::stack_ring::StackRing;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// Ring size used by the driver's static rings (64K slots).
+pub const BENCH_RING_SIZE: usize = 1 << 16;
+/// Upper bound on producer/consumer pairs the driver supports.
+pub const BENCH_MAX_PAIRS: usize = 8;
+
+// StackRing::new is const, so the driver's rings live in statics;
+// reset() reopens them between runs. The mutex makes the driver safe to
+// call from concurrent contexts (e.g. parallel tests) — runs on the
+// shared statics must not overlap.
+static RINGS: [StackRing<u32, BENCH_RING_SIZE>; BENCH_MAX_PAIRS] =
+    [const { StackRing::new() }; BENCH_MAX_PAIRS];
+static DRIVER_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// One benchmark scenario for [`run`].
+pub struct BenchConfig {
+    /// Producer/consumer pair count (max [`BENCH_MAX_PAIRS`]).
+    pub producers: usize,
+    /// Messages each producer pushes per run.
+    pub msgs_per_producer: u64,
+    /// Reservation batch size (1 = per-message commit).
+    pub batch: usize,
+    /// Pin producers to CPUs `0..n` and consumers to `n..2n`.
+    pub pinned: bool,
+    /// Untimed runs before measuring.
+    pub warmup_runs: usize,
+    /// Timed runs aggregated into the result.
+    pub bench_runs: usize,
+    /// Simulated per-message consumer work, as a busy-spin of this many
+    /// nanoseconds in the handler (0 = flat-out). Flat-out runs keep the
+    /// ring L1/L2-resident and overstate throughput; a realistic work
+    /// cost shows where the ring size must grow to absorb bursts.
+    pub work_ns: u64,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            producers: 1,
+            msgs_per_producer: 100_000_000,
+            batch: 1,
+            pinned: false,
+            warmup_runs: 2,
+            bench_runs: 5,
+            work_ns: 0,
+        }
+    }
+}
+
+/// Aggregate outcome of a [`run`], in messages per nanosecond (B/s).
+pub struct BenchResult {
+    /// Median throughput over the timed runs.
+    pub throughput: f64,
+    /// Population standard deviation over the timed runs.
+    pub stddev: f64,
+    /// The individual per-run rates, for custom analysis.
+    pub runs: Vec<f64>,
+    /// Pin attempts that failed across all runs. Non-zero on a
+    /// `pinned: true` scenario means the numbers are really (partly)
+    /// unpinned — label them accordingly.
+    pub pin_failures: usize,
+}
+
+/// Run one scenario: warmups, then timed runs, aggregated with
+/// [`median_stddev`]. Panics if `producers` exceeds the driver's static
+/// ring count.
+pub fn run(config: &BenchConfig) -> BenchResult {
+    assert!(config.producers >= 1 && config.producers <= BENCH_MAX_PAIRS);
+    let _guard = DRIVER_LOCK.lock().unwrap();
+    let pin_failures = AtomicUsize::new(0);
+
+    for _ in 0..config.warmup_runs {
+        let _ = run_once_typed(&RINGS, config, &pin_failures);
+    }
+    // Warmup pin failures would repeat identically in the timed runs;
+    // count only the latter so the total is interpretable.
+    pin_failures.store(0, Ordering::Relaxed);
+
+    let runs: Vec<f64> = (0..config.bench_runs)
+        .map(|_| run_once_typed(&RINGS, config, &pin_failures))
+        .collect();
+    let (throughput, stddev) = median_stddev(&runs);
+    BenchResult {
+        throughput,
+        stddev,
+        runs,
+        pin_failures: pin_failures.load(Ordering::Relaxed),
+    }
+}
+
+/// One timed pass over `producers` pairs; returns messages per ns.
+pub fn run_once(config: &BenchConfig) -> f64 {
+    let _guard = DRIVER_LOCK.lock().unwrap();
+    run_once_typed(&RINGS, config, &AtomicUsize::new(0))
+}
+
+/// Run one scenario on caller-supplied rings of any payload type, so
+/// workloads can be characterized with their real element size (a
+/// 64-byte market-data struct behaves nothing like a `u32` in cache).
+/// `T: Default` supplies the written value; the measurement is about
+/// bytes moved, not contents. The caller owns exclusive access to
+/// `rings` for the duration.
+pub fn run_typed<T, const N: usize>(
+    rings: &'static [StackRing<T, N>],
+    config: &BenchConfig,
+) -> BenchResult
+where
+    T: Copy + Default + Send + Sync + 'static,
+{
+    assert!(config.producers >= 1 && config.producers <= rings.len());
+    let pin_failures = AtomicUsize::new(0);
+
+    for _ in 0..config.warmup_runs {
+        let _ = run_once_typed(rings, config, &pin_failures);
+    }
+    pin_failures.store(0, Ordering::Relaxed);
+
+    let runs: Vec<f64> = (0..config.bench_runs)
+        .map(|_| run_once_typed(rings, config, &pin_failures))
+        .collect();
+    let (throughput, stddev) = median_stddev(&runs);
+    BenchResult {
+        throughput,
+        stddev,
+        runs,
+        pin_failures: pin_failures.load(Ordering::Relaxed),
+    }
+}
+
+fn run_once_typed<T, const N: usize>(
+    all_rings: &'static [StackRing<T, N>],
+    config: &BenchConfig,
+    pin_failures: &AtomicUsize,
+) -> f64
+where
+    T: Copy + Default + Send + Sync + 'static,
+{
+    let num_pairs = config.producers;
+    let msgs = config.msgs_per_producer;
+    let batch = config.batch.max(1);
+
+    let rings: Vec<&'static StackRing<T, N>> = all_rings[..num_pairs].iter().collect();
+    for ring in &rings {
+        unsafe { ring.reset() };
+    }
+
+    let counts: Arc<Vec<AtomicU64>> = Arc::new((0..num_pairs).map(|_| AtomicU64::new(0)).collect());
+    let fails: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+
+    let t0 = Instant::now();
+
+    let mut consumer_threads = Vec::with_capacity(num_pairs);
+    for i in 0..num_pairs {
+        let ring = rings[i];
+        let counts_clone = counts.clone();
+        let fails = fails.clone();
+        let cpu_id = num_pairs + i;
+        let pinned = config.pinned;
+        let work_ns = config.work_ns;
+        consumer_threads.push(thread::spawn(move || {
+            if pinned && !pin_to_cpu(cpu_id) {
+                fails.fetch_add(1, Ordering::Relaxed);
+            }
+            let mut count = 0u64;
+            loop {
+                unsafe {
+                    // The work_ns branch inside the handler is
+                    // perfectly predicted; it costs nothing in the
+                    // flat-out configuration.
+                    let n = ring.consume_batch(|_| {
+                        if work_ns > 0 {
+                            busy_spin(work_ns);
+                        }
+                    });
+                    if n > 0 {
+                        count += n as u64;
+                    } else if ring.is_closed() && ring.is_empty() {
+                        break;
+                    } else {
+                        std::hint::spin_loop();
+                    }
+                }
+            }
+            counts_clone[i].store(count, Ordering::Release);
+        }));
+    }
+
+    let mut producer_threads = Vec::with_capacity(num_pairs);
+    for (i, ring) in rings.iter().copied().enumerate() {
+        let fails = fails.clone();
+        let pinned = config.pinned;
+        producer_threads.push(thread::spawn(move || {
+            if pinned && !pin_to_cpu(i) {
+                fails.fetch_add(1, Ordering::Relaxed);
+            }
+            let value = T::default();
+            let mut sent = 0u64;
+            while sent < msgs {
+                let want = (batch as u64).min(msgs - sent) as usize;
+                unsafe {
+                    if let Some((ptr, len)) = ring.reserve(want) {
+                        for j in 0..len {
+                            *ptr.add(j) = value;
+                        }
+                        ring.commit(len);
+                        sent += len as u64;
+                    } else {
+                        std::hint::spin_loop();
+                    }
+                }
+            }
+            ring.close();
+        }));
+    }
+
+    for t in producer_threads {
+        t.join().unwrap();
+    }
+    for t in consumer_threads {
+        t.join().unwrap();
+    }
+
+    let ns = t0.elapsed().as_nanos() as f64;
+    pin_failures.fetch_add(fails.load(Ordering::Relaxed), Ordering::Relaxed);
+    let total: u64 = counts.iter().map(|c| c.load(Ordering::Acquire)).sum();
+    total as f64 / ns
+}
+
+/// Sampled-latency slots per pair for [`run_latency`]. Together with the
+/// sample period this must exceed the ring capacity, so a produce stamp
+/// can never be overwritten before its message is consumed.
+const LATENCY_SLOTS: usize = 1024;
+
+/// Log2-bucketed latency histogram with 16 linear sub-buckets per power
+/// of two — HDR-style resolution (±6%) without a dependency.
+pub struct LatencyHistogram {
+    buckets: Vec<u64>,
+    count: u64,
+    max_ns: u64,
+}
+
+impl LatencyHistogram {
+    const SUB: usize = 16;
+
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; 64 * Self::SUB],
+            count: 0,
+            max_ns: 0,
+        }
+    }
+
+    fn index(ns: u64) -> usize {
+        if ns < Self::SUB as u64 {
+            return ns as usize;
+        }
+        let exp = 63 - ns.leading_zeros() as usize;
+        let sub = (ns >> (exp - 4)) as usize & (Self::SUB - 1);
+        exp * Self::SUB + sub
+    }
+
+    pub fn record(&mut self, ns: u64) {
+        self.buckets[Self::index(ns)] += 1;
+        self.count += 1;
+        self.max_ns = self.max_ns.max(ns);
+    }
+
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        for (a, b) in self.buckets.iter_mut().zip(&other.buckets) {
+            *a += b;
+        }
+        self.count += other.count;
+        self.max_ns = self.max_ns.max(other.max_ns);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Approximate value at percentile `p` in [0, 100]; representative
+    /// (lower-bound) value of the bucket the rank falls into.
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let rank = ((p / 100.0) * self.count as f64).ceil().max(1.0) as u64;
+        let mut seen = 0u64;
+        for (i, &c) in self.buckets.iter().enumerate() {
+            seen += c;
+            if seen >= rank {
+                // Values below SUB are stored exactly at their own index
+                if i < Self::SUB {
+                    return i as u64;
+                }
+                let exp = i / Self::SUB;
+                let sub = (i % Self::SUB) as u64;
+                return (1u64 << exp) + (sub << (exp - 4));
+            }
+        }
+        self.max_ns
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Timed pass that additionally samples end-to-end latency: every
+/// `sample_every`-th message gets a produce timestamp in a side-channel
+/// slot, and the consumer records `consume_time - produce_time` into a
+/// histogram. Returns the merged histogram; print p50/p99/p99.9 from it.
+/// Tail latency is what a throughput table hides — a batching change can
+/// raise B/s while parking messages for microseconds.
+pub fn run_latency(config: &BenchConfig, sample_every: u64) -> LatencyHistogram {
+    let num_pairs = config.producers;
+    assert!(num_pairs >= 1 && num_pairs <= BENCH_MAX_PAIRS);
+    // A stamp slot must outlive its message's time in the ring.
+    assert!(sample_every as usize * LATENCY_SLOTS > BENCH_RING_SIZE);
+
+    let _guard = DRIVER_LOCK.lock().unwrap();
+
+    let msgs = config.msgs_per_producer;
+    let batch = config.batch.max(1);
+
+    let rings: Vec<&'static StackRing<u32, BENCH_RING_SIZE>> =
+        RINGS[..num_pairs].iter().collect();
+    for ring in &rings {
+        unsafe { ring.reset() };
+    }
+
+    let stamps: Arc<Vec<Vec<AtomicU64>>> = Arc::new(
+        (0..num_pairs)
+            .map(|_| (0..LATENCY_SLOTS).map(|_| AtomicU64::new(0)).collect())
+            .collect(),
+    );
+    let fails: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    let t0 = Instant::now();
+
+    let mut consumer_threads = Vec::with_capacity(num_pairs);
+    for i in 0..num_pairs {
+        let ring = rings[i];
+        let stamps = stamps.clone();
+        let fails = fails.clone();
+        let cpu_id = num_pairs + i;
+        let pinned = config.pinned;
+        let work_ns = config.work_ns;
+        consumer_threads.push(thread::spawn(move || {
+            if pinned && !pin_to_cpu(cpu_id) {
+                fails.fetch_add(1, Ordering::Relaxed);
+            }
+            let mut hist = LatencyHistogram::new();
+            let mut count = 0u64;
+            loop {
+                unsafe {
+                    let n = ring.consume_batch(|v| {
+                        let seq = *v as u64;
+                        if seq % sample_every == 0 {
+                            let slot = (seq / sample_every) as usize % LATENCY_SLOTS;
+                            let produced = stamps[i][slot].load(Ordering::Acquire);
+                            let now = t0.elapsed().as_nanos() as u64;
+                            hist.record(now.saturating_sub(produced));
+                        }
+                        if work_ns > 0 {
+                            busy_spin(work_ns);
+                        }
+                    });
+                    if n > 0 {
+                        count += n as u64;
+                    } else if ring.is_closed() && ring.is_empty() {
+                        break;
+                    } else {
+                        std::hint::spin_loop();
+                    }
+                }
+            }
+            let _ = count;
+            hist
+        }));
+    }
+
+    let mut producer_threads = Vec::with_capacity(num_pairs);
+    for (i, ring) in rings.iter().copied().enumerate() {
+        let stamps = stamps.clone();
+        let fails = fails.clone();
+        let pinned = config.pinned;
+        producer_threads.push(thread::spawn(move || {
+            if pinned && !pin_to_cpu(i) {
+                fails.fetch_add(1, Ordering::Relaxed);
+            }
+            let mut sent = 0u64;
+            while sent < msgs {
+                let want = (batch as u64).min(msgs - sent) as usize;
+                unsafe {
+                    if let Some((ptr, len)) = ring.reserve(want) {
+                        for j in 0..len {
+                            let seq = sent + j as u64;
+                            if seq % sample_every == 0 {
+                                let slot = (seq / sample_every) as usize % LATENCY_SLOTS;
+                                stamps[i][slot]
+                                    .store(t0.elapsed().as_nanos() as u64, Ordering::Release);
+                            }
+                            *ptr.add(j) = seq as u32;
+                        }
+                        ring.commit(len);
+                        sent += len as u64;
+                    } else {
+                        std::hint::spin_loop();
+                    }
+                }
+            }
+            ring.close();
+        }));
+    }
+
+    for t in producer_threads {
+        t.join().unwrap();
+    }
+
+    let mut merged = LatencyHistogram::new();
+    for t in consumer_threads {
+        merged.merge(&t.join().unwrap());
+    }
+    let failed = fails.load(Ordering::Relaxed);
+    if failed > 0 {
+        eprintln!(
+            "warning: {} of {} threads could not be pinned; latency numbers are (partly) unpinned",
+            failed,
+            2 * num_pairs
+        );
+    }
+    merged
+}
+
+/// Median and population standard deviation of a set of rates.
+pub fn median_stddev(rates: &[f64]) -> (f64, f64) {
+    let mut sorted = rates.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
+    } else {
+        sorted[sorted.len() / 2]
+    };
+
+    let mean: f64 = rates.iter().sum::<f64>() / rates.len() as f64;
+    let variance: f64 = rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / rates.len() as f64;
+    let stddev = variance.sqrt();
+
+    (median, stddev)
+}
+
+/// Busy-spin for roughly `ns` nanoseconds — the driver's stand-in for
+/// per-message consumer work. Clock-based rather than a calibrated
+/// iteration count, so it stays honest across frequency scaling; the
+/// `Instant` read costs ~20 ns, which bounds the useful resolution.
+#[inline]
+pub fn busy_spin(ns: u64) {
+    let start = Instant::now();
+    while (start.elapsed().as_nanos() as u64) < ns {
+        std::hint::spin_loop();
+    }
+}
+
+/// Pin the current thread to the given logical CPU. Returns whether the
+/// pin actually took effect — a 4P4C run on a 4-core box can't pin 8
+/// threads, and silently unpinned results masquerading as "Pinned" are
+/// worse than no pinning at all.
+pub fn pin_to_cpu(cpu_id: usize) -> bool {
+    if let Some(core_ids) = core_affinity::get_core_ids() {
+        if cpu_id < core_ids.len() {
+            return core_affinity::set_for_current(core_ids[cpu_id]);
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let mut hist = LatencyHistogram::new();
+        for ns in 1..=1000u64 {
+            hist.record(ns);
+        }
+        assert_eq!(hist.count(), 1000);
+
+        // Log bucketing is approximate: p50 of 1..=1000 is ~500, and the
+        // bucket resolution is 1/16th of the power of two (±6%)
+        let p50 = hist.percentile(50.0);
+        assert!((450..=550).contains(&p50), "p50 = {}", p50);
+        let p99 = hist.percentile(99.0);
+        assert!((900..=1000).contains(&p99), "p99 = {}", p99);
+    }
+
+    #[test]
+    fn test_latency_driver_smoke() {
+        let hist = run_latency(
+            &BenchConfig {
+                producers: 1,
+                msgs_per_producer: 300_000,
+                batch: 16,
+                pinned: false,
+                warmup_runs: 0,
+                bench_runs: 1,
+                work_ns: 0,
+            },
+            256,
+        );
+        assert!(hist.count() > 0);
+        assert!(hist.percentile(99.0) >= hist.percentile(50.0));
+    }
+
+    #[test]
+    fn test_driver_smoke() {
+        let result = run(&BenchConfig {
+            producers: 2,
+            msgs_per_producer: 10_000,
+            batch: 64,
+            pinned: false,
+            warmup_runs: 0,
+            bench_runs: 3,
+            work_ns: 0,
+        });
+        assert!(result.throughput > 0.0);
+        assert_eq!(result.runs.len(), 3);
+    }
+}
@@ -1,17 +1,29 @@
 //! A/B Test Benchmark for RingMPSC optimizations
 //! Tests different configurations: prefetch vs no-prefetch, pinning vs no-pinning
 
+use rust_impl::bench_util::{run, run_latency, run_typed, BenchConfig};
 use rust_impl::stack_ring::StackRing;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
-use std::thread;
-use std::time::Instant;
 
 const MSG: u64 = 100_000_000; // 100M messages per producer
-const RING_SIZE: usize = 1 << 16; // 64K slots
 const WARMUP_RUNS: usize = 2;
 const BENCH_RUNS: usize = 5;
 
+/// Realistic 64-byte payload — one full cache line per message, unlike
+/// the `u32` default where 32 messages share a line.
+#[derive(Clone, Copy, Default)]
+#[repr(C, align(64))]
+#[allow(dead_code)] // written wholesale by the driver, never read per-field
+struct MarketData {
+    ts: u64,
+    price: u64,
+    qty: u64,
+    flags: u64,
+    pad: [u64; 4],
+}
+
+const WIDE_RING_SIZE: usize = 1 << 14; // 16K slots × 64 B = 1 MiB
+static WIDE_RINGS: [StackRing<MarketData, WIDE_RING_SIZE>; 1] = [const { StackRing::new() }];
+
 fn main() {
     println!("\n═══════════════════════════════════════════════════════════════");
     println!("║                   RINGMPSC - A/B TEST BENCHMARK              ║");
@@ -19,7 +31,7 @@ fn main() {
     println!(
         "Config: {}M msgs/producer, ring={}K slots",
         MSG / 1_000_000,
-        RING_SIZE >> 10
+        rust_impl::bench_util::BENCH_RING_SIZE >> 10
     );
     println!(
         "Warmup: {} runs, Benchmark: {} runs (median reported)\n",
@@ -41,136 +53,132 @@ fn main() {
     println!("├──────────────┼───────────────┼──────────────┼─────────────┤");
 
     let mut last_rate = 0.0f64;
+    let mut unpinned_note = false;
     for (name, pairs, pinned) in configs {
-        let rates = run_benchmark(pairs, pinned);
-        let (median, stddev) = stats(&rates);
+        let result = run(&BenchConfig {
+            producers: pairs,
+            msgs_per_producer: MSG,
+            batch: 1,
+            pinned,
+            warmup_runs: WARMUP_RUNS,
+            bench_runs: BENCH_RUNS,
+            work_ns: 0,
+        });
 
         let improvement = if last_rate > 0.0 && pairs == configs[configs.len() - 2].1 {
-            format!("{:+.1}%", (median / last_rate - 1.0) * 100.0)
+            format!("{:+.1}%", (result.throughput / last_rate - 1.0) * 100.0)
         } else {
             "-".to_string()
         };
 
+        // A "Pinned" row where pinning actually failed is worse than an
+        // unpinned one: label it honestly.
+        let label = if pinned && result.pin_failures > 0 {
+            format!("{}*", name)
+        } else {
+            name.to_string()
+        };
         println!(
             "│ {:12} │ {:>8.3} B/s  │ ±{:6.3} B/s  │ {:>10}  │",
-            name, median, stddev, improvement
+            label, result.throughput, result.stddev, improvement
         );
+        if pinned && result.pin_failures > 0 {
+            unpinned_note = true;
+        }
 
         if !pinned {
-            last_rate = median;
+            last_rate = result.throughput;
         }
     }
 
-    println!("└──────────────┴───────────────┴──────────────┴─────────────┘\n");
-}
-
-fn run_benchmark(num_pairs: usize, pinned: bool) -> Vec<f64> {
-    // Warmup
-    for _ in 0..WARMUP_RUNS {
-        let _ = run_test(num_pairs, pinned);
+    println!("└──────────────┴───────────────┴──────────────┴─────────────┘");
+    if unpinned_note {
+        println!("* some threads could not be pinned; treat as unpinned");
     }
+    println!();
+
+    // Payload-size A/B: same driver, monomorphized per element type. A
+    // 64-byte element moves 16x the bytes per message of a u32.
+    let wide = run_typed(
+        &WIDE_RINGS,
+        &BenchConfig {
+            producers: 1,
+            msgs_per_producer: MSG / 10,
+            batch: 256,
+            pinned: true,
+            warmup_runs: 1,
+            bench_runs: BENCH_RUNS,
+            work_ns: 0,
+        },
+    );
+    println!(
+        "Payload A/B (1P1C pinned, batch=256): 64-byte element {:.3} B/s (±{:.3})\n",
+        wide.throughput, wide.stddev
+    );
 
-    // Actual runs
-    (0..BENCH_RUNS)
-        .map(|_| run_test(num_pairs, pinned))
-        .collect()
-}
-
-fn run_test(num_pairs: usize, pinned: bool) -> f64 {
-    let rings: Vec<&'static StackRing<u32, RING_SIZE>> = (0..num_pairs)
-        .map(|_| &*Box::leak(Box::new(StackRing::new())))
-        .collect();
-
-    let counts: Arc<Vec<AtomicU64>> = Arc::new((0..num_pairs).map(|_| AtomicU64::new(0)).collect());
-
-    let t0 = Instant::now();
-
-    // Start consumers (pinned to CPUs num_pairs..2*num_pairs)
-    let mut consumer_threads = Vec::with_capacity(num_pairs);
-    for i in 0..num_pairs {
-        let ring = rings[i];
-        let counts_clone = counts.clone();
-        let cpu_id = num_pairs + i;
-        consumer_threads.push(thread::spawn(move || {
-            if pinned {
-                pin_to_cpu(cpu_id);
-            }
-            let mut count = 0u64;
-            loop {
-                unsafe {
-                    let n = ring.consume_batch(|_| {});
-                    if n > 0 {
-                        count += n as u64;
-                    } else if ring.is_closed() && ring.is_empty() {
-                        break;
-                    } else {
+    // Commit-batching A/B: AutoCommitter threshold trades tail-store
+    // traffic for visibility latency. threshold=1 is the per-item
+    // baseline the SPSC table above measures.
+    const BATCH_MSGS: u64 = MSG / 10;
+    for threshold in [1usize, 16, 256] {
+        let ring: rust_impl::Ring<u64> = rust_impl::Ring::new(14);
+        let start = std::time::Instant::now();
+        std::thread::scope(|s| {
+            s.spawn(|| unsafe {
+                let mut ac = ring.auto_committer(threshold);
+                for i in 0..BATCH_MSGS {
+                    loop {
+                        if let Some(r) = ac.reserve(1) {
+                            (r.ptr as *mut u64).write(i);
+                            ac.commit(1);
+                            break;
+                        }
                         std::hint::spin_loop();
                     }
                 }
-            }
-            counts_clone[i].store(count, Ordering::Release);
-        }));
-    }
-
-    // Start producers (pinned to CPUs 0..num_pairs)
-    let mut producer_threads = Vec::with_capacity(num_pairs);
-    for i in 0..num_pairs {
-        let ring = rings[i];
-        producer_threads.push(thread::spawn(move || {
-            if pinned {
-                pin_to_cpu(i);
-            }
-            let mut sent = 0u64;
-            while sent < MSG {
-                unsafe {
-                    if let Some((ptr, len)) = ring.reserve(1) {
-                        *ptr = sent as u32;
-                        ring.commit(len);
-                        sent += len as u64;
-                    } else {
+            });
+            s.spawn(|| unsafe {
+                let mut seen = 0u64;
+                while seen < BATCH_MSGS {
+                    let n = ring.consume_batch(|_| {}) as u64;
+                    if n == 0 {
                         std::hint::spin_loop();
                     }
+                    seen += n;
                 }
-            }
-            ring.close();
-        }));
-    }
-
-    for t in producer_threads {
-        t.join().unwrap();
-    }
-    for t in consumer_threads {
-        t.join().unwrap();
-    }
-
-    let elapsed = t0.elapsed();
-    let ns = elapsed.as_nanos() as f64;
-    let total: u64 = counts.iter().map(|c| c.load(Ordering::Acquire)).sum();
-
-    total as f64 / ns
-}
-
-fn pin_to_cpu(cpu_id: usize) {
-    if let Some(core_ids) = core_affinity::get_core_ids() {
-        if cpu_id < core_ids.len() {
-            core_affinity::set_for_current(core_ids[cpu_id]);
-        }
+            });
+        });
+        let secs = start.elapsed().as_secs_f64();
+        println!(
+            "Commit batching (1P1C, threshold={:>3}): {:.3} B/s",
+            threshold,
+            BATCH_MSGS as f64 / secs / 1e9
+        );
     }
-}
-
-fn stats(rates: &[f64]) -> (f64, f64) {
-    let mut sorted = rates.to_vec();
-    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
-    let median = if sorted.len() % 2 == 0 {
-        (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
-    } else {
-        sorted[sorted.len() / 2]
-    };
-
-    let mean: f64 = rates.iter().sum::<f64>() / rates.len() as f64;
-    let variance: f64 = rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / rates.len() as f64;
-    let stddev = variance.sqrt();
-
-    (median, stddev)
+    println!();
+
+    // End-to-end latency for the pinned 1P1C case: the tail is what the
+    // throughput table can't show
+    let hist = run_latency(
+        &BenchConfig {
+            producers: 1,
+            msgs_per_producer: MSG / 10,
+            batch: 1,
+            pinned: true,
+            warmup_runs: 0,
+            bench_runs: 1,
+            work_ns: 0,
+        },
+        1024,
+    );
+    println!(
+        "Latency (1P1C pinned, 1/1024 sampled, {} samples):",
+        hist.count()
+    );
+    println!(
+        "  p50 = {} ns, p99 = {} ns, p99.9 = {} ns\n",
+        hist.percentile(50.0),
+        hist.percentile(99.0),
+        hist.percentile(99.9)
+    );
 }
@@ -0,0 +1,257 @@
+//! Bounded lock-free MPMC ring (Vyukov's algorithm).
+//!
+//! The SPSC rings get away with two shared cursors because each side
+//! has exactly one owner. True MPMC instead gives every slot its own
+//! sequence number: producers and consumers CAS a global position to
+//! claim a slot, then use the slot's sequence to know when the claimed
+//! slot is actually theirs to write or read. No slot is ever touched by
+//! two threads at once, and the queue never locks.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// One queue slot: `seq` encodes the slot's state machine.
+/// `seq == pos` — free, a producer at `pos` may claim it;
+/// `seq == pos + 1` — full, a consumer at `pos` may claim it;
+/// anything else — another lap's traffic, retry or report full/empty.
+struct Slot<T> {
+    seq: AtomicU64,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Bounded multi-producer multi-consumer ring with per-slot sequence
+/// numbers. Unlike the SPSC rings there is no reserve/commit region
+/// API: elements move by value through [`push`](Self::push) and
+/// [`pop`](Self::pop), because neighboring slots can belong to
+/// different threads mid-flight.
+#[repr(C)]
+pub struct MpmcRing<T, const N: usize> {
+    // Producers and consumers contend on different cache lines.
+    enqueue_pos: CacheLinePadded<AtomicU64>,
+    dequeue_pos: CacheLinePadded<AtomicU64>,
+    buffer: [Slot<T>; N],
+}
+
+#[repr(C)]
+#[repr(align(128))]
+struct CacheLinePadded<T>(T);
+
+// SAFETY: values cross threads by ownership transfer only — a slot is
+// written by exactly one claiming producer and read (moved out) by
+// exactly one claiming consumer, so `T: Send` is the whole requirement.
+unsafe impl<T: Send, const N: usize> Send for MpmcRing<T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for MpmcRing<T, N> {}
+
+impl<T, const N: usize> MpmcRing<T, N> {
+    const MASK: u64 = N as u64 - 1;
+
+    /// Create a new ring; `N` must be a power of two.
+    pub fn new() -> Self {
+        assert!(N > 0 && (N & (N - 1)) == 0, "N must be a power of 2");
+        Self {
+            enqueue_pos: CacheLinePadded(AtomicU64::new(0)),
+            dequeue_pos: CacheLinePadded(AtomicU64::new(0)),
+            // Slot i starts one lap ready for the producer at position i
+            buffer: std::array::from_fn(|i| Slot {
+                seq: AtomicU64::new(i as u64),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }),
+        }
+    }
+
+    /// Slot count `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Enqueue from any thread; returns the value back when the queue
+    /// is full at the claimed position.
+    pub fn push(&self, value: T) -> Result<(), T> {
+        let mut pos = self.enqueue_pos.0.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.buffer[(pos & Self::MASK) as usize];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let dif = seq as i64 - pos as i64;
+            if dif == 0 {
+                // Slot is free for this lap; claim the position.
+                match self.enqueue_pos.0.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // The claim made this slot exclusively ours.
+                        unsafe { (*slot.value.get()).write(value) };
+                        // Publish: consumers at `pos` see seq == pos + 1
+                        slot.seq.store(pos.wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if dif < 0 {
+                // The slot still holds last lap's value: full.
+                return Err(value);
+            } else {
+                // Another producer claimed this position; catch up.
+                pos = self.enqueue_pos.0.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Dequeue from any thread; `None` when empty at the claimed
+    /// position.
+    pub fn pop(&self) -> Option<T> {
+        let mut pos = self.dequeue_pos.0.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.buffer[(pos & Self::MASK) as usize];
+            let seq = slot.seq.load(Ordering::Acquire);
+            let dif = seq as i64 - pos.wrapping_add(1) as i64;
+            if dif == 0 {
+                match self.dequeue_pos.0.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        let value = unsafe { (*slot.value.get()).assume_init_read() };
+                        // Free the slot for the producer one lap ahead
+                        slot.seq
+                            .store(pos.wrapping_add(N as u64), Ordering::Release);
+                        return Some(value);
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if dif < 0 {
+                return None;
+            } else {
+                pos = self.dequeue_pos.0.load(Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Whether the queue looked empty at the moment of the check — a
+    /// snapshot, stale by the time it returns under contention.
+    pub fn is_empty(&self) -> bool {
+        let deq = self.dequeue_pos.0.load(Ordering::Relaxed);
+        let enq = self.enqueue_pos.0.load(Ordering::Relaxed);
+        enq == deq
+    }
+}
+
+impl<T, const N: usize> Default for MpmcRing<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for MpmcRing<T, N> {
+    fn drop(&mut self) {
+        // Anything still queued is live and owned by the ring.
+        while self.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_push_pop_roundtrip() {
+        let ring: MpmcRing<u64, 4> = MpmcRing::new();
+        assert!(ring.is_empty());
+        assert_eq!(ring.pop(), None);
+
+        for i in 0..4u64 {
+            assert!(ring.push(i).is_ok());
+        }
+        // Full: push hands the value back
+        assert_eq!(ring.push(99), Err(99));
+
+        for i in 0..4u64 {
+            assert_eq!(ring.pop(), Some(i));
+        }
+        assert_eq!(ring.pop(), None);
+
+        // Several laps to exercise the sequence wraparound
+        for i in 0..32u64 {
+            assert!(ring.push(i).is_ok());
+            assert_eq!(ring.pop(), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_drop_releases_queued_values() {
+        let payload = Arc::new(());
+        {
+            let ring: MpmcRing<Arc<()>, 8> = MpmcRing::new();
+            for _ in 0..3 {
+                assert!(ring.push(payload.clone()).is_ok());
+            }
+            assert_eq!(Arc::strong_count(&payload), 4);
+        }
+        assert_eq!(Arc::strong_count(&payload), 1);
+    }
+
+    #[test]
+    fn test_mpmc_concurrent_sum() {
+        const PER_PRODUCER: u64 = 20_000;
+        let ring: Arc<MpmcRing<u64, 1024>> = Arc::new(MpmcRing::new());
+        let sum = Arc::new(AtomicU64::new(0));
+        let taken = Arc::new(AtomicU64::new(0));
+
+        let producers: Vec<_> = (0..2)
+            .map(|p| {
+                let ring = ring.clone();
+                std::thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        let mut v = p * PER_PRODUCER + i;
+                        loop {
+                            match ring.push(v) {
+                                Ok(()) => break,
+                                Err(back) => {
+                                    v = back;
+                                    std::thread::yield_now();
+                                }
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..2)
+            .map(|_| {
+                let ring = ring.clone();
+                let sum = sum.clone();
+                let taken = taken.clone();
+                std::thread::spawn(move || loop {
+                    if taken.load(Ordering::Relaxed) >= 2 * PER_PRODUCER {
+                        break;
+                    }
+                    match ring.pop() {
+                        Some(v) => {
+                            sum.fetch_add(v, Ordering::Relaxed);
+                            taken.fetch_add(1, Ordering::Relaxed);
+                        }
+                        None => std::thread::yield_now(),
+                    }
+                })
+            })
+            .collect();
+
+        for t in producers {
+            t.join().unwrap();
+        }
+        for t in consumers {
+            t.join().unwrap();
+        }
+
+        let total = 2 * PER_PRODUCER;
+        assert_eq!(taken.load(Ordering::Relaxed), total);
+        assert_eq!(sum.load(Ordering::Relaxed), total * (total - 1) / 2);
+    }
+}
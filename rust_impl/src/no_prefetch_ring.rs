@@ -0,0 +1,147 @@
+//! Baseline SPSC ring without prefetch hints, kept as the "B" side of the
+//! prefetch A/B comparison (`bench_prefetch` bin and the criterion
+//! `throughput` bench). Identical cursor protocol to `StackRing`, minus
+//! the prefetch call sites, so any throughput delta is attributable to
+//! prefetching alone.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// A `StackRing` twin with no prefetch instructions anywhere.
+#[repr(C)]
+#[repr(align(128))]
+pub struct NoPrefetchRing<T, const N: usize> {
+    tail: AtomicU64,
+    cached_head: UnsafeCell<u64>,
+    head: AtomicU64,
+    _pad: [u8; 120],
+    cached_tail: UnsafeCell<u64>,
+    closed: AtomicBool,
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+// SAFETY: same contract as StackRing — Sync requires T: Sync because
+// consume_batch hands out &T produced on another thread.
+unsafe impl<T: Send, const N: usize> Send for NoPrefetchRing<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Sync for NoPrefetchRing<T, N> {}
+
+impl<T, const N: usize> NoPrefetchRing<T, N> {
+    const MASK: usize = N - 1;
+
+    pub const fn new() -> Self {
+        assert!(N > 0 && (N & (N - 1)) == 0, "N must be a power of 2");
+        Self {
+            tail: AtomicU64::new(0),
+            cached_head: UnsafeCell::new(0),
+            head: AtomicU64::new(0),
+            _pad: [0; 120],
+            cached_tail: UnsafeCell::new(0),
+            closed: AtomicBool::new(false),
+            buffer: unsafe { MaybeUninit::uninit().assume_init() },
+        }
+    }
+
+    /// Reserve space for writing n elements (no prefetch issued).
+    ///
+    /// # Safety
+    /// Single producer only; the returned region is uninitialized.
+    #[inline(always)]
+    pub unsafe fn reserve(&self, n: usize) -> Option<(*mut T, usize)> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let cached_head_ptr = self.cached_head.get();
+        let mut head = *cached_head_ptr;
+
+        let used = tail.wrapping_sub(head);
+        let mut free = (N as u64).wrapping_sub(used);
+
+        if free < (n as u64) {
+            head = self.head.load(Ordering::Acquire);
+            *cached_head_ptr = head;
+            let used = tail.wrapping_sub(head);
+            free = (N as u64).wrapping_sub(used);
+            if free < (n as u64) {
+                return None;
+            }
+        }
+
+        let idx = (tail as usize) & Self::MASK;
+        let contiguous = n.min(N - idx);
+        // NO PREFETCH HERE!
+        let ptr = (*self.buffer.as_ptr().add(idx)).get() as *mut T;
+        Some((ptr, contiguous))
+    }
+
+    /// Commit n elements that were written.
+    #[inline(always)]
+    pub fn commit(&self, n: usize) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        self.tail
+            .store(tail.wrapping_add(n as u64), Ordering::Release);
+    }
+
+    /// Consume all available items in batch (no prefetch issued).
+    ///
+    /// # Safety
+    /// Single consumer only.
+    #[inline(always)]
+    pub unsafe fn consume_batch<F>(&self, mut handler: F) -> usize
+    where
+        F: FnMut(&T),
+    {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+
+        let avail = tail.wrapping_sub(head);
+        if avail == 0 {
+            return 0;
+        }
+
+        let mut pos = head;
+        while pos != tail {
+            let idx = (pos as usize) & Self::MASK;
+            let ptr = (*self.buffer.as_ptr().add(idx)).get() as *const T;
+            handler(&*ptr);
+            pos = pos.wrapping_add(1);
+        }
+
+        self.head.store(pos, Ordering::Release);
+        *self.cached_tail.get() = tail;
+        avail as usize
+    }
+
+    /// Check if the ring is closed.
+    #[inline(always)]
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Check if the ring is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.tail.load(Ordering::Relaxed) == self.head.load(Ordering::Relaxed)
+    }
+
+    /// Close the ring (signals consumers).
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+
+    /// Reopen and empty the ring between runs (see `StackRing::reset`).
+    ///
+    /// # Safety
+    /// No producer or consumer may be running concurrently.
+    pub unsafe fn reset(&self) {
+        self.tail.store(0, Ordering::Relaxed);
+        self.head.store(0, Ordering::Relaxed);
+        *self.cached_head.get() = 0;
+        *self.cached_tail.get() = 0;
+        self.closed.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<T, const N: usize> Default for NoPrefetchRing<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -12,11 +12,28 @@ use std::ops::Deref;
 use std::ptr::NonNull;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+/// Bail out on a refcount that passed `isize::MAX`. Kept out of line so
+/// the overflow check in `clone` stays a compare+branch on the hot path.
+/// Aborts like `std::sync::Arc` by default; the `overflow-panic` cargo
+/// feature switches to an unwindable panic for embedders that supervise
+/// the process and prefer to lose a thread over the whole process.
+#[cold]
+#[inline(never)]
+fn refcount_overflow() -> ! {
+    #[cfg(feature = "overflow-panic")]
+    panic!("RawArc refcount overflow");
+    #[cfg(not(feature = "overflow-panic"))]
+    std::process::abort();
+}
+
 /// The inner allocation containing refcount and data.
 /// Using repr(C) ensures predictable layout: refcount first, then data.
+/// `T: ?Sized` so the same header works for slice payloads
+/// (`RawArcInner<[T]>`), where `data` is the inline tail of the
+/// allocation.
 #[repr(C)]
 #[repr(align(128))]
-struct RawArcInner<T> {
+struct RawArcInner<T: ?Sized> {
     refcount: AtomicUsize,
     data: T,
 }
@@ -30,13 +47,13 @@ struct RawArcInner<T> {
 ///   Ptr -> RawArcInner { refcount, data: T }
 ///
 /// This eliminates one level of pointer chasing on the hot path.
-pub struct RawArc<T> {
+pub struct RawArc<T: ?Sized> {
     ptr: NonNull<RawArcInner<T>>,
     _marker: PhantomData<RawArcInner<T>>,
 }
 
-unsafe impl<T: Send + Sync> Send for RawArc<T> {}
-unsafe impl<T: Send + Sync> Sync for RawArc<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Send for RawArc<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for RawArc<T> {}
 
 impl<T> RawArc<T> {
     /// Create a new RawArc with the given value.
@@ -62,28 +79,237 @@ impl<T> RawArc<T> {
         }
     }
 
+
+    /// Reconstruct an owner from a bare data pointer, bumping the
+    /// refcount. For FFI callbacks that were handed only the
+    /// [`as_ptr`](Self::as_ptr) value: `RawArcInner` is `repr(C)` with
+    /// the refcount ahead of the data, so the header is recoverable by
+    /// subtracting the field offset.
+    ///
+    /// # Safety
+    /// `data` must have come from `as_ptr`/`as_mut_ptr` of a `RawArc<T>`
+    /// whose refcount is still at least 1 for the duration of this call.
+    pub unsafe fn incref_from_data_ptr(data: *const T) -> RawArc<T> {
+        let offset = std::mem::offset_of!(RawArcInner<T>, data);
+        let inner = (data as *const u8).sub(offset) as *mut RawArcInner<T>;
+
+        let old = (*inner).refcount.fetch_add(1, Ordering::Relaxed);
+        if old > isize::MAX as usize {
+            refcount_overflow();
+        }
+
+        RawArc {
+            ptr: NonNull::new_unchecked(inner),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Allocate the aligned refcount+data block without constructing a
+    /// `T`, mirroring `Arc::new_uninit`. Lets a large payload — a
+    /// `Ring<T>` with a big buffer, say — be initialized in place through
+    /// [`as_mut_ptr`](Self::as_mut_ptr) instead of moved by value into
+    /// [`new`](Self::new).
+    pub fn new_uninit() -> RawArc<std::mem::MaybeUninit<T>> {
+        let layout = Layout::new::<RawArcInner<std::mem::MaybeUninit<T>>>();
+
+        unsafe {
+            let ptr = alloc(layout) as *mut RawArcInner<std::mem::MaybeUninit<T>>;
+            if ptr.is_null() {
+                std::alloc::handle_alloc_error(layout);
+            }
+
+            // Only the refcount is initialized; data stays uninit.
+            std::ptr::addr_of_mut!((*ptr).refcount).write(AtomicUsize::new(1));
+
+            RawArc {
+                ptr: NonNull::new_unchecked(ptr),
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+impl<T> RawArc<T> {
+    /// [`new_uninit`](Self::new_uninit) composed with in-place
+    /// construction: allocates the aligned block, hands the payload
+    /// slot to `init` to build `T` at its final heap address, and
+    /// returns the finished arc. Spares large payloads — a `Ring`'s
+    /// aligned control block, say — the stack construction and memcpy
+    /// that [`new`](Self::new) implies.
+    ///
+    /// # Safety
+    /// `init` must leave the slot fully initialized; an unwritten field
+    /// is UB as soon as the arc is read or dropped.
+    pub unsafe fn emplace(init: impl FnOnce(&mut std::mem::MaybeUninit<T>)) -> Self {
+        let uninit = Self::new_uninit();
+        init(&mut *uninit.as_mut_ptr());
+        uninit.assume_init()
+    }
+}
+
+impl<T> RawArc<std::mem::MaybeUninit<T>> {
+    /// Convert to `RawArc<T>` once the contents have been written.
+    ///
+    /// # Safety
+    /// The data must be fully initialized, e.g. through
+    /// [`as_mut_ptr`](Self::as_mut_ptr). `RawArcInner<MaybeUninit<T>>`
+    /// and `RawArcInner<T>` share one `repr(C)` layout, so this is a
+    /// pointer cast; the refcount carries over.
+    pub unsafe fn assume_init(self) -> RawArc<T> {
+        let ptr = self.ptr.cast::<RawArcInner<T>>();
+        std::mem::forget(self);
+        RawArc {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> RawArc<[T]> {
+    /// Allocate a refcount header plus `len` uninitialized inline
+    /// elements — `new_uninit` for slice payloads. The elements are
+    /// contiguous in one allocation, so initializing them in place
+    /// (e.g. via [`as_mut_ptr`](Self::as_mut_ptr)) yields an arena the
+    /// consumer can walk sequentially instead of chasing `len`
+    /// scattered allocations.
+    pub fn new_uninit_slice(len: usize) -> RawArc<[std::mem::MaybeUninit<T>]> {
+        let offset = std::mem::offset_of!(RawArcInner<[T; 0]>, data);
+        let align = std::mem::align_of::<RawArcInner<[T; 0]>>();
+        let size = offset + std::mem::size_of::<T>() * len;
+        let layout = Layout::from_size_align(size, align)
+            .expect("RawArc slice layout overflow")
+            .pad_to_align();
+
+        unsafe {
+            let raw = alloc(layout);
+            if raw.is_null() {
+                std::alloc::handle_alloc_error(layout);
+            }
+
+            let inner = std::ptr::slice_from_raw_parts_mut(
+                raw as *mut std::mem::MaybeUninit<T>,
+                len,
+            ) as *mut RawArcInner<[std::mem::MaybeUninit<T>]>;
+
+            std::ptr::addr_of_mut!((*inner).refcount).write(AtomicUsize::new(1));
+
+            RawArc {
+                ptr: NonNull::new_unchecked(inner),
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+impl<T> RawArc<[std::mem::MaybeUninit<T>]> {
+    /// Convert to `RawArc<[T]>` once every element has been written;
+    /// the slice-payload counterpart of
+    /// [`RawArc::<T>::assume_init`](RawArc::assume_init).
+    ///
+    /// # Safety
+    /// All `len` elements must be fully initialized.
+    pub unsafe fn assume_init(self) -> RawArc<[T]> {
+        let ptr = NonNull::new_unchecked(self.ptr.as_ptr() as *mut RawArcInner<[T]>);
+        std::mem::forget(self);
+        RawArc {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Copy> RawArc<[T]> {
+    /// Create a `RawArc<[T]>` holding a copy of `data` inline: one
+    /// allocation carries the refcount header and the slice tail, so
+    /// sharing a fixed-size buffer costs a single indirection instead of
+    /// the two in `RawArc<Vec<T>>`.
+    ///
+    /// The layout is computed by hand from the `repr(C)` header: the
+    /// slice starts at the `data` offset of `RawArcInner<[T; 0]>` (same
+    /// alignment as `[T]`, so the offset matches the unsized layout).
+    pub fn from_slice(data: &[T]) -> Self {
+        let offset = std::mem::offset_of!(RawArcInner<[T; 0]>, data);
+        let align = std::mem::align_of::<RawArcInner<[T; 0]>>();
+        let size = offset + std::mem::size_of_val(data);
+        let layout = Layout::from_size_align(size, align)
+            .expect("RawArc slice layout overflow")
+            .pad_to_align();
+
+        unsafe {
+            let raw = alloc(layout);
+            if raw.is_null() {
+                std::alloc::handle_alloc_error(layout);
+            }
+
+            // Attach the length metadata by round-tripping through a
+            // slice pointer with the same base address.
+            let inner = std::ptr::slice_from_raw_parts_mut(raw as *mut T, data.len())
+                as *mut RawArcInner<[T]>;
+
+            std::ptr::addr_of_mut!((*inner).refcount).write(AtomicUsize::new(1));
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                std::ptr::addr_of_mut!((*inner).data) as *mut T,
+                data.len(),
+            );
+
+            RawArc {
+                ptr: NonNull::new_unchecked(inner),
+                _marker: PhantomData,
+            }
+        }
+    }
+}
+
+impl<T: ?Sized> RawArc<T> {
     /// Get a raw pointer to the inner data.
     /// This is useful for performance-critical code that needs direct access.
     #[inline(always)]
     pub fn as_ptr(&self) -> *const T {
-        unsafe { &(*self.ptr.as_ptr()).data as *const T }
+        unsafe { std::ptr::addr_of!((*self.ptr.as_ptr()).data) }
     }
 
     /// Get a mutable raw pointer to the inner data.
     /// SAFETY: Caller must ensure exclusive access.
     #[inline(always)]
     pub fn as_mut_ptr(&self) -> *mut T {
-        unsafe { &mut (*self.ptr.as_ptr()).data as *mut T }
+        unsafe { std::ptr::addr_of_mut!((*self.ptr.as_ptr()).data) }
     }
 
     /// Get the current reference count.
+    ///
+    /// Alias of [`strong_count`](Self::strong_count), kept for backwards
+    /// compatibility with existing callers.
     #[inline]
     pub fn ref_count(&self) -> usize {
+        self.strong_count()
+    }
+
+    /// Get the current reference count (`Relaxed` load).
+    ///
+    /// Named for parity with `Arc::strong_count`. The relaxed ordering is
+    /// fine for diagnostics and rough accounting, but the value can be
+    /// stale with respect to other threads' work — do not make ownership
+    /// decisions on it; use
+    /// [`strong_count_acquire`](Self::strong_count_acquire) for that.
+    #[inline]
+    pub fn strong_count(&self) -> usize {
         unsafe { (*self.ptr.as_ptr()).refcount.load(Ordering::Relaxed) }
     }
+
+    /// Reference count with an `Acquire` load, for callers deciding based
+    /// on the value (e.g. "am I the last owner?"): observing a count of 1
+    /// here synchronizes with the `Release` decrement in `Drop`, so all
+    /// writes made by former owners are visible afterwards. Slightly more
+    /// expensive than the `Relaxed` fast path; prefer `strong_count` when
+    /// the number is only informational.
+    #[inline]
+    pub fn strong_count_acquire(&self) -> usize {
+        unsafe { (*self.ptr.as_ptr()).refcount.load(Ordering::Acquire) }
+    }
 }
 
-impl<T> Clone for RawArc<T> {
+impl<T: ?Sized> Clone for RawArc<T> {
     #[inline]
     fn clone(&self) -> Self {
         // Increment refcount with Relaxed ordering.
@@ -96,9 +322,10 @@ impl<T> Clone for RawArc<T> {
                 .refcount
                 .fetch_add(1, Ordering::Relaxed);
 
-            // Overflow check (same as std::sync::Arc)
+            // Overflow check (same bound as std::sync::Arc); the
+            // abort-vs-panic choice lives in refcount_overflow
             if old > isize::MAX as usize {
-                std::process::abort();
+                refcount_overflow();
             }
         }
 
@@ -109,7 +336,7 @@ impl<T> Clone for RawArc<T> {
     }
 }
 
-impl<T> Deref for RawArc<T> {
+impl<T: ?Sized> Deref for RawArc<T> {
     type Target = T;
 
     #[inline(always)]
@@ -118,7 +345,51 @@ impl<T> Deref for RawArc<T> {
     }
 }
 
-impl<T> Drop for RawArc<T> {
+// Forwarding impls so RawArc<T> behaves like its inner T the way Arc
+// does: comparisons, ordering and hashing go through the pointee (two
+// distinct allocations holding equal values compare equal), and
+// formatting is transparent. This is what lets a RawArc<T> serve as a
+// map key or be printed directly.
+
+impl<T: ?Sized + std::fmt::Display> std::fmt::Display for RawArc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + std::fmt::Debug> std::fmt::Debug for RawArc<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for RawArc<T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + Eq> Eq for RawArc<T> {}
+
+impl<T: ?Sized + PartialOrd> PartialOrd for RawArc<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + Ord> Ord for RawArc<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ?Sized + std::hash::Hash> std::hash::Hash for RawArc<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: ?Sized> Drop for RawArc<T> {
     fn drop(&mut self) {
         unsafe {
             // Decrement with Release ordering to ensure all writes are visible
@@ -135,8 +406,10 @@ impl<T> Drop for RawArc<T> {
             // before we deallocate.
             std::sync::atomic::fence(Ordering::Acquire);
 
-            // Drop the inner value and deallocate
-            let layout = Layout::new::<RawArcInner<T>>();
+            // Drop the inner value and deallocate. Layout::for_value
+            // recovers the allocation size from the (possibly fat)
+            // pointer, matching what new/from_slice allocated.
+            let layout = Layout::for_value(self.ptr.as_ref());
             std::ptr::drop_in_place(self.ptr.as_ptr());
             dealloc(self.ptr.as_ptr() as *mut u8, layout);
         }
@@ -165,6 +438,114 @@ mod tests {
         assert_eq!(arc1.ref_count(), 1);
     }
 
+    #[test]
+    fn test_strong_count_naming() {
+        let arc = RawArc::new(7u32);
+        assert_eq!(arc.strong_count(), 1);
+        assert_eq!(arc.ref_count(), arc.strong_count());
+
+        let arc2 = arc.clone();
+        assert_eq!(arc.strong_count_acquire(), 2);
+        drop(arc2);
+        assert_eq!(arc.strong_count_acquire(), 1);
+    }
+
+    #[test]
+    fn test_new_uninit_assume_init() {
+        let uninit = RawArc::<[u64; 4]>::new_uninit();
+        unsafe {
+            uninit.as_mut_ptr().write(std::mem::MaybeUninit::new([1, 2, 3, 4]));
+            let arc = uninit.assume_init();
+            assert_eq!(*arc, [1, 2, 3, 4]);
+            assert_eq!(arc.ref_count(), 1);
+
+            let arc2 = arc.clone();
+            assert_eq!(arc2.ref_count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_emplace_constructs_in_place() {
+        let arc = unsafe {
+            RawArc::<[u64; 4]>::emplace(|slot| {
+                let p = slot.as_mut_ptr() as *mut u64;
+                for i in 0..4 {
+                    p.add(i).write(i as u64 * 2);
+                }
+            })
+        };
+        assert_eq!(*arc, [0, 2, 4, 6]);
+        assert_eq!(arc.ref_count(), 1);
+    }
+
+    #[test]
+    fn test_new_uninit_slice_in_place_init() {
+        let uninit = RawArc::<[u64]>::new_uninit_slice(5);
+        let arc = unsafe {
+            let base = uninit.as_mut_ptr() as *mut u64;
+            for i in 0..5 {
+                base.add(i).write(i as u64);
+            }
+            uninit.assume_init()
+        };
+        assert_eq!(&*arc, &[0, 1, 2, 3, 4]);
+        assert_eq!(arc.ref_count(), 1);
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let arc: RawArc<[u32]> = RawArc::from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(arc.len(), 5);
+        assert_eq!(&*arc, &[1, 2, 3, 4, 5]);
+        assert_eq!(arc.as_ptr() as *const u32 as usize % 8, 0);
+
+        let arc2 = arc.clone();
+        assert_eq!(arc.ref_count(), 2);
+        drop(arc);
+        assert_eq!(&arc2[1..3], &[2, 3]);
+    }
+
+    #[test]
+    fn test_from_slice_empty() {
+        let arc: RawArc<[u64]> = RawArc::from_slice(&[]);
+        assert!(arc.is_empty());
+        assert_eq!(arc.ref_count(), 1);
+    }
+
+    #[test]
+    fn test_incref_from_data_ptr() {
+        let arc = RawArc::new(99u64);
+        let data = arc.as_ptr();
+
+        let arc2 = unsafe { RawArc::incref_from_data_ptr(data) };
+        assert_eq!(*arc2, 99);
+        assert_eq!(arc.ref_count(), 2);
+
+        drop(arc);
+        assert_eq!(arc2.ref_count(), 1);
+        assert_eq!(*arc2, 99);
+    }
+
+    #[test]
+    fn test_forwarding_impls() {
+        let a = RawArc::new(3u64);
+        let b = RawArc::new(3u64);
+        let c = RawArc::new(5u64);
+
+        // Value equality across distinct allocations
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a < c);
+
+        assert_eq!(format!("{}", a), "3");
+        assert_eq!(format!("{:?}", c), "5");
+
+        // Usable as a map key
+        let mut map = std::collections::HashMap::new();
+        map.insert(a, "three");
+        assert_eq!(map.get(&b), Some(&"three"));
+    }
+
     #[test]
     fn test_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -0,0 +1,165 @@
+//! Live, stable-ABI ring control block for cross-language consumers.
+//!
+//! Unlike the Zig `RingHeader` (a frozen point-in-time snapshot), this
+//! struct is the thing both sides attach to: the cursors are atomics, so
+//! a C or C++ process mapping the same shared memory can follow the
+//! producer and free space back to it while the ring is running. The
+//! layout is mirrored field-for-field by `include/ringmpsc.h`; any change
+//! here must bump [`RingHeader::VERSION`] and the header file together.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+/// Fixed-layout control block: one 128-byte line per cursor, then a cold
+/// line for capacity/version/closed. 384 bytes total, 128-byte aligned.
+///
+/// The cursors are free-running u64s; an index is `cursor & (capacity-1)`
+/// (capacity is always a power of two). `tail` is producer-owned and
+/// published with release; `head` is consumer-owned, ditto. The data
+/// region conventionally follows the header in the same mapping.
+#[repr(C)]
+#[repr(align(128))]
+pub struct RingHeader {
+    /// Consumer cursor (free-running, wraps modulo 2^64).
+    pub head: AtomicU64,
+    _pad0: [u8; 120],
+    /// Producer cursor (free-running, wraps modulo 2^64).
+    pub tail: AtomicU64,
+    _pad1: [u8; 120],
+    /// Slot count of the ring the header describes.
+    pub capacity: u64,
+    /// Layout version, always [`RingHeader::VERSION`].
+    pub version: u32,
+    /// 0 = open, 1 = closed.
+    pub closed: AtomicU32,
+}
+
+impl RingHeader {
+    /// Layout version stamped into `version`; kept in lockstep with
+    /// `RINGMPSC_HEADER_VERSION` in `include/ringmpsc.h` and
+    /// `HEADER_VERSION` on the Zig snapshot type.
+    pub const VERSION: u32 = 1;
+
+    /// Create an open header for a ring of `capacity` slots.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is not a power of two — the masking contract
+    /// in the C header depends on it.
+    pub fn new(capacity: u64) -> Self {
+        assert!(
+            capacity > 0 && capacity & (capacity - 1) == 0,
+            "capacity must be a power of 2"
+        );
+        Self {
+            head: AtomicU64::new(0),
+            _pad0: [0; 120],
+            tail: AtomicU64::new(0),
+            _pad1: [0; 120],
+            capacity,
+            version: Self::VERSION,
+            closed: AtomicU32::new(0),
+        }
+    }
+
+    /// Initialize a header in place, e.g. at the start of a fresh
+    /// shared-memory mapping.
+    ///
+    /// # Safety
+    /// `ptr` must be valid for writes of `size_of::<RingHeader>()` bytes,
+    /// 128-byte aligned, and not concurrently accessed until this returns.
+    pub unsafe fn init_at(ptr: *mut RingHeader, capacity: u64) {
+        ptr.write(Self::new(capacity));
+    }
+
+    /// Validate a header read from foreign memory: version and capacity
+    /// must match what this build understands.
+    pub fn is_valid(&self) -> bool {
+        self.version == Self::VERSION
+            && self.capacity > 0
+            && self.capacity & (self.capacity - 1) == 0
+    }
+
+    /// Items currently in the ring (producer view may lag by design).
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.tail
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.head.load(Ordering::Acquire))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Publish `n` produced items (producer side).
+    #[inline]
+    pub fn publish(&self, n: u64) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        self.tail.store(tail.wrapping_add(n), Ordering::Release);
+    }
+
+    /// Free `n` consumed items back to the producer (consumer side).
+    #[inline]
+    pub fn advance(&self, n: u64) {
+        let head = self.head.load(Ordering::Relaxed);
+        self.head.store(head.wrapping_add(n), Ordering::Release);
+    }
+
+    /// Close the ring (either side; consumers drain then stop).
+    pub fn close(&self) {
+        self.closed.store(1, Ordering::Release);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire) != 0
+    }
+}
+
+// The ABI contract the C header mirrors, checked at compile time.
+const _: () = {
+    assert!(std::mem::size_of::<RingHeader>() == 384);
+    assert!(std::mem::align_of::<RingHeader>() == 128);
+    assert!(std::mem::offset_of!(RingHeader, head) == 0);
+    assert!(std::mem::offset_of!(RingHeader, tail) == 128);
+    assert!(std::mem::offset_of!(RingHeader, capacity) == 256);
+    assert!(std::mem::offset_of!(RingHeader, version) == 264);
+    assert!(std::mem::offset_of!(RingHeader, closed) == 268);
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let h = RingHeader::new(1024);
+        assert!(h.is_valid());
+        assert!(h.is_empty());
+        assert!(!h.is_closed());
+
+        h.publish(3);
+        assert_eq!(h.len(), 3);
+        h.advance(2);
+        assert_eq!(h.len(), 1);
+
+        h.close();
+        assert!(h.is_closed());
+    }
+
+    #[test]
+    fn test_init_at() {
+        let mut slot = std::mem::MaybeUninit::<RingHeader>::uninit();
+        unsafe {
+            RingHeader::init_at(slot.as_mut_ptr(), 64);
+            let h = slot.assume_init_ref();
+            assert!(h.is_valid());
+            assert_eq!(h.capacity, 64);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "power of 2")]
+    fn test_rejects_non_pow2() {
+        let _ = RingHeader::new(100);
+    }
+}
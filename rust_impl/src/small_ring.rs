@@ -0,0 +1,166 @@
+//! Inline-or-heap SPSC ring, smallvec-style.
+//!
+//! `Ring<T>` always heap-allocates; `StackRing<T, N>` is inline but
+//! fixes the capacity at compile time. `SmallRing<T, INLINE>` picks at
+//! construction: a requested capacity up to `INLINE` uses the embedded
+//! buffer (no allocation, no pointer load), anything larger falls back
+//! to a heap `Ring`.
+
+use crate::stack_ring::StackRing;
+use crate::Ring;
+
+/// SPSC ring with `INLINE` embedded slots and a heap fallback for
+/// larger runtime capacities. The API mirrors `StackRing`'s
+/// pointer/length style; every call dispatches on one enum tag, which
+/// predicts perfectly since the variant never changes after `new`.
+pub struct SmallRing<T, const INLINE: usize> {
+    storage: Storage<T, INLINE>,
+}
+
+enum Storage<T, const INLINE: usize> {
+    Inline(StackRing<T, INLINE>),
+    Heap(Ring<T>),
+}
+
+impl<T, const INLINE: usize> SmallRing<T, INLINE> {
+    /// Create a ring with at least `min_slots` capacity: inline when it
+    /// fits `INLINE`, heap otherwise (rounded up to a power of two).
+    pub fn new(min_slots: usize) -> Self {
+        let storage = if min_slots <= INLINE {
+            Storage::Inline(StackRing::new())
+        } else {
+            Storage::Heap(Ring::with_capacity(min_slots))
+        };
+        Self { storage }
+    }
+
+    /// Whether the buffer is embedded (no heap allocation happened).
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline(_))
+    }
+
+    /// Actual slot count (`INLINE`, or the rounded-up heap capacity).
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline(_) => INLINE,
+            Storage::Heap(r) => r.capacity,
+        }
+    }
+
+    /// Reserve space for writing n elements; see [`StackRing::reserve`].
+    ///
+    /// # Safety
+    /// Single producer only.
+    #[inline(always)]
+    pub unsafe fn reserve(&self, n: usize) -> Option<(*mut T, usize)> {
+        match &self.storage {
+            Storage::Inline(r) => r.reserve(n),
+            Storage::Heap(r) => r.reserve(n).map(|res| (res.ptr as *mut T, res.len)),
+        }
+    }
+
+    /// Commit n elements that were written.
+    #[inline(always)]
+    pub fn commit(&self, n: usize) {
+        match &self.storage {
+            Storage::Inline(r) => r.commit(n),
+            Storage::Heap(r) => r.commit(n),
+        }
+    }
+
+    /// Peek at available data for reading; see [`StackRing::peek`].
+    ///
+    /// # Safety
+    /// Single consumer only.
+    #[inline(always)]
+    pub unsafe fn peek(&self) -> (*const T, usize) {
+        match &self.storage {
+            Storage::Inline(r) => r.peek(),
+            Storage::Heap(r) => r.peek(),
+        }
+    }
+
+    /// Advance the read pointer by n elements.
+    #[inline(always)]
+    pub fn advance(&self, n: usize) {
+        match &self.storage {
+            Storage::Inline(r) => r.advance(n),
+            Storage::Heap(r) => r.advance(n),
+        }
+    }
+
+    /// Consume all available items in batch.
+    ///
+    /// # Safety
+    /// Single consumer only.
+    #[inline(always)]
+    pub unsafe fn consume_batch<F>(&self, handler: F) -> usize
+    where
+        F: FnMut(&T),
+    {
+        match &self.storage {
+            Storage::Inline(r) => r.consume_batch(handler),
+            Storage::Heap(r) => r.consume_batch(handler),
+        }
+    }
+
+    /// Check if the ring is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        match &self.storage {
+            Storage::Inline(r) => r.is_empty(),
+            Storage::Heap(r) => r.is_empty(),
+        }
+    }
+
+    /// Check if the ring is closed.
+    #[inline(always)]
+    pub fn is_closed(&self) -> bool {
+        match &self.storage {
+            Storage::Inline(r) => r.is_closed(),
+            Storage::Heap(r) => r.is_closed(),
+        }
+    }
+
+    /// Close the ring (signals consumers).
+    pub fn close(&self) {
+        match &self.storage {
+            Storage::Inline(r) => r.close(),
+            Storage::Heap(r) => r.close(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_vs_heap_selection() {
+        let small: SmallRing<u64, 64> = SmallRing::new(16);
+        assert!(small.is_inline());
+        assert_eq!(small.capacity(), 64);
+
+        let big: SmallRing<u64, 64> = SmallRing::new(100);
+        assert!(!big.is_inline());
+        assert_eq!(big.capacity(), 128);
+    }
+
+    #[test]
+    fn test_roundtrip_both_variants() {
+        for min_slots in [4usize, 100] {
+            let ring: SmallRing<u64, 8> = SmallRing::new(min_slots);
+            unsafe {
+                for i in 0..3u64 {
+                    let (ptr, _) = ring.reserve(1).unwrap();
+                    *ptr = i;
+                    ring.commit(1);
+                }
+                let mut got = Vec::new();
+                ring.consume_batch(|v| got.push(*v));
+                assert_eq!(got, vec![0, 1, 2]);
+                assert!(ring.is_empty());
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@
 
 use std::cell::UnsafeCell;
 use std::mem::MaybeUninit;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 
 // Prefetch intrinsics - kept for potential use in batched operations
 #[allow(unused_imports)]
@@ -16,6 +16,32 @@ use crate::atomics::{prefetch_read, prefetch_write};
 /// Unlike `Ring<T>` which uses a heap-allocated buffer via `buffer_ptr`,
 /// `StackRing` embeds the buffer directly, making `buffer[idx]` a simple
 /// base+offset calculation that the compiler can constant-fold.
+///
+/// The ring is `Sync` only for `T: Send + Sync`: `peek` and
+/// `consume_batch` hand out `&T` on the consumer thread while the value
+/// was written on the producer thread, so interior-mutable types like
+/// `Cell` must be rejected:
+///
+/// ```compile_fail
+/// use rust_impl::stack_ring::StackRing;
+/// fn assert_sync<T: Sync>() {}
+/// assert_sync::<StackRing<std::cell::Cell<u32>, 64>>();
+/// ```
+///
+/// `new` is `const`, so a fixed global ring can live in a `static`
+/// directly — no heap allocation or `Box::leak` needed for `'static`:
+///
+/// ```
+/// use rust_impl::stack_ring::StackRing;
+/// static RING: StackRing<u32, 1024> = StackRing::new();
+///
+/// unsafe {
+///     let (ptr, _) = RING.reserve(1).unwrap();
+///     *ptr = 7;
+/// }
+/// RING.commit(1);
+/// assert!(!RING.is_empty());
+/// ```
 #[repr(C)]
 pub struct StackRing<T, const N: usize> {
     // === Producer hot path (cache line 1) ===
@@ -30,7 +56,11 @@ pub struct StackRing<T, const N: usize> {
     closed: AtomicBool,
 
     // === Buffer (inline, no pointer indirection) ===
-    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+    // Cache-line aligned like the heap ring's buffer, so the slot
+    // pointers reserve/peek hand out satisfy any align_of::<T>() up to
+    // 128 — SIMD loads through them are safe by construction. Slots
+    // past the base are aligned by the array layout itself.
+    buffer: CacheLinePadded<[UnsafeCell<MaybeUninit<T>>; N]>,
 }
 
 /// Wrapper to force cache line alignment
@@ -46,8 +76,20 @@ impl<T> std::ops::Deref for CacheLinePadded<T> {
     }
 }
 
+impl<T> std::ops::DerefMut for CacheLinePadded<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+// SAFETY: moving the ring moves the owned `T`s, so `T: Send` suffices for
+// `Send`. `Sync` additionally needs `T: Sync` because `peek` and
+// `consume_batch` hand out `&T` on the consumer thread while the value was
+// produced on another — `T: Send` alone would let a `Cell`-style type be
+// aliased across threads (see the compile_fail doctest on the type).
 unsafe impl<T: Send, const N: usize> Send for StackRing<T, N> {}
-unsafe impl<T: Send, const N: usize> Sync for StackRing<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Sync for StackRing<T, N> {}
 
 impl<T, const N: usize> StackRing<T, N> {
     /// Mask for wrapping indices (N must be power of 2)
@@ -68,17 +110,63 @@ impl<T, const N: usize> StackRing<T, N> {
             cached_tail: UnsafeCell::new(0),
             closed: AtomicBool::new(false),
             // SAFETY: MaybeUninit doesn't require initialization
-            buffer: unsafe { MaybeUninit::uninit().assume_init() },
+            buffer: CacheLinePadded(unsafe { MaybeUninit::uninit().assume_init() }),
         }
     }
 
+    /// Compile-time budget guard for cache-resident rings: panics at
+    /// const-eval if the whole struct (buffer + control words) exceeds
+    /// `BYTES`. Catches a large `T` times a big `N` at build time
+    /// instead of as cache thrashing in production:
+    ///
+    /// ```
+    /// use rust_impl::stack_ring::StackRing;
+    /// const _: () = StackRing::<u64, 1024>::assert_fits_in::<32768>();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use rust_impl::stack_ring::StackRing;
+    /// // 64 KiB of u64 slots can't fit an L1-sized 32 KiB budget
+    /// const _: () = StackRing::<u64, 8192>::assert_fits_in::<32768>();
+    /// ```
+    pub const fn assert_fits_in<const BYTES: usize>() {
+        assert!(
+            std::mem::size_of::<Self>() <= BYTES,
+            "StackRing exceeds its byte budget"
+        );
+    }
+
+    /// Slot count `N`, exposed as a `const fn` so companion arrays can
+    /// be sized from an existing ring in const context.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Index-wrapping mask (`N - 1`).
+    #[inline(always)]
+    pub const fn mask() -> usize {
+        Self::MASK
+    }
+
     /// Reserve space for writing n elements.
     /// Returns a pointer to the start of the reserved region and its length.
+    /// The pointer is always aligned for `T`: the buffer base is 128-byte
+    /// aligned and slots follow array layout, so any `align_of::<T>()` up
+    /// to a cache line is satisfied at every index.
     /// Note: Software prefetch is intentionally disabled as A/B testing showed
     /// the hardware prefetcher handles sequential access patterns better on
     /// modern AMD Zen 4 cores.
     #[inline(always)]
     pub unsafe fn reserve(&self, n: usize) -> Option<(*mut T, usize)> {
+        // An n larger than the ring can never succeed; catch the buggy
+        // call in debug builds instead of letting the producer spin on
+        // a permanently failing reserve.
+        debug_assert!(n <= N, "reserve({}) can never succeed on a {}-slot ring", n, N);
+        if n > N {
+            return None;
+        }
+
         let tail = self.tail.load(Ordering::Relaxed);
 
         let cached_head_ptr = self.cached_head.get();
@@ -109,10 +197,47 @@ impl<T, const N: usize> StackRing<T, N> {
     #[inline(always)]
     pub fn commit(&self, n: usize) {
         let tail = self.tail.load(Ordering::Relaxed);
+        // Catch commits past the reserved space in debug builds; a
+        // fresh head load can only under-report free space.
+        #[cfg(debug_assertions)]
+        {
+            let head = self.head.load(Ordering::Acquire);
+            let free = (N as u64).wrapping_sub(tail.wrapping_sub(head));
+            debug_assert!(
+                n as u64 <= free,
+                "commit({}) exceeds the {} free slots",
+                n,
+                free
+            );
+        }
         self.tail
             .store(tail.wrapping_add(n as u64), Ordering::Release);
     }
 
+    /// Push that always succeeds: when the ring is full, the oldest
+    /// element is dropped and its slot overwritten — the "last N
+    /// samples" telemetry buffer, where recent data always beats old.
+    ///
+    /// # Safety
+    /// Sound only single-threaded or strict SPSC where this caller is
+    /// *both* ends: evicting the oldest element moves head, which is
+    /// otherwise the consumer's cursor — a concurrent consumer would
+    /// race the eviction and read a slot mid-overwrite.
+    pub unsafe fn push_overwrite(&self, value: T) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Relaxed);
+        if tail.wrapping_sub(head) == N as u64 {
+            // Full: evict the oldest so the write below can't land on
+            // a slot a reader still considers live.
+            let idx = (head as usize) & Self::MASK;
+            std::ptr::drop_in_place((*self.buffer.as_ptr().add(idx)).get() as *mut T);
+            self.head.store(head.wrapping_add(1), Ordering::Release);
+        }
+        let idx = (tail as usize) & Self::MASK;
+        ((*self.buffer.as_ptr().add(idx)).get() as *mut T).write(value);
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+    }
+
     /// Peek at available data for reading.
     /// Returns a pointer to readable data and its length.
     #[inline(always)]
@@ -138,6 +263,23 @@ impl<T, const N: usize> StackRing<T, N> {
         (ptr, contiguous)
     }
 
+    /// [`peek`](Self::peek) with the `slice::from_raw_parts` wrapping
+    /// done here instead of at every call site: returns the contiguous
+    /// readable run as `&[T]`, empty when there's nothing to read.
+    ///
+    /// # Safety
+    /// Single consumer only. The slice borrows the ring's buffer and is
+    /// valid until the consumer calls [`advance`](Self::advance); reading
+    /// it after advancing past those slots races the producer.
+    #[inline(always)]
+    pub unsafe fn peek_slice(&self) -> &[T] {
+        let (ptr, len) = self.peek();
+        if len == 0 {
+            return &[];
+        }
+        std::slice::from_raw_parts(ptr, len)
+    }
+
     /// Consume all available items in batch.
     /// This amortizes the cost of the atomic head update.
     #[inline(always)]
@@ -188,10 +330,120 @@ impl<T, const N: usize> StackRing<T, N> {
         self.tail.load(Ordering::Relaxed) == self.head.load(Ordering::Relaxed)
     }
 
+    /// Whether every slot is occupied. Uses an `Acquire` head load so
+    /// back-pressure polling sees a fresh answer without the side
+    /// effects of a failed `reserve`.
+    #[inline(always)]
+    pub fn is_full(&self) -> bool {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head) == N as u64
+    }
+
     /// Close the ring (signals consumers).
     pub fn close(&self) {
         self.closed.store(true, Ordering::Release);
     }
+
+    /// Reset to the freshly-constructed state so a `static` ring can be
+    /// reused across benchmark runs without reallocating. Any items still
+    /// in the buffer are abandoned without being dropped.
+    ///
+    /// # Safety
+    /// No producer or consumer may be running concurrently.
+    pub unsafe fn reset(&self) {
+        self.tail.store(0, Ordering::Relaxed);
+        self.head.store(0, Ordering::Relaxed);
+        *self.cached_head.get() = 0;
+        *self.cached_tail.get() = 0;
+        self.closed.store(false, Ordering::Relaxed);
+    }
+
+    /// Reset to the freshly-constructed state, dropping any elements
+    /// still in `[head, tail)` first — the safe single-threaded
+    /// counterpart to [`reset`](Self::reset), correct for owning
+    /// payloads like `Box<_>`. `&mut self` guarantees no producer or
+    /// consumer is running. The `closed` flag is reset to open, like
+    /// a brand-new ring.
+    pub fn clear(&mut self) {
+        let mut pos = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        while pos != tail {
+            let idx = (pos as usize) & Self::MASK;
+            unsafe {
+                std::ptr::drop_in_place((*self.buffer.as_mut_ptr().add(idx)).get_mut().as_mut_ptr());
+            }
+            pos = pos.wrapping_add(1);
+        }
+        self.tail.store(0, Ordering::Relaxed);
+        self.head.store(0, Ordering::Relaxed);
+        *self.cached_head.get_mut() = 0;
+        *self.cached_tail.get_mut() = 0;
+        self.closed.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<T: Copy, const N: usize> StackRing<T, N> {
+    /// Copy (without advancing) up to `dst.len()` readable items into
+    /// `dst`, splitting the copy across the buffer wrap; returns how
+    /// many were copied. The non-consuming counterpart to the heap
+    /// ring's `consume_into`: snapshot the front of the queue into a
+    /// scratch buffer, inspect it, then [`advance`](Self::advance) by
+    /// however many were accepted.
+    ///
+    /// # Safety
+    /// Single consumer only.
+    pub unsafe fn peek_into(&self, dst: &mut [T]) -> usize {
+        let head = self.head.load(Ordering::Relaxed);
+
+        let cached_tail_ptr = self.cached_tail.get();
+        let mut tail = *cached_tail_ptr;
+
+        if head == tail {
+            tail = self.tail.load(Ordering::Acquire);
+            *cached_tail_ptr = tail;
+            if head == tail {
+                return 0;
+            }
+        }
+
+        let avail = tail.wrapping_sub(head) as usize;
+        let take = avail.min(dst.len());
+        let idx = (head as usize) & Self::MASK;
+        let first = take.min(N - idx);
+
+        std::ptr::copy_nonoverlapping(
+            (*self.buffer.as_ptr().add(idx)).get() as *const T,
+            dst.as_mut_ptr(),
+            first,
+        );
+        if take > first {
+            // Wrapped remainder starts back at slot zero
+            std::ptr::copy_nonoverlapping(
+                (*self.buffer.as_ptr()).get() as *const T,
+                dst.as_mut_ptr().add(first),
+                take - first,
+            );
+        }
+        take
+    }
+}
+
+impl<T, const N: usize> Drop for StackRing<T, N> {
+    fn drop(&mut self) {
+        // Unconsumed elements in [head, tail) are live and must be
+        // dropped, or a StackRing<Box<_>, N> leaks on drop. &mut self
+        // guarantees no producer/consumer is running.
+        let mut pos = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        while pos != tail {
+            let idx = (pos as usize) & Self::MASK;
+            unsafe {
+                std::ptr::drop_in_place((*self.buffer.as_mut_ptr().add(idx)).get_mut().as_mut_ptr());
+            }
+            pos = pos.wrapping_add(1);
+        }
+    }
 }
 
 impl<T, const N: usize> Default for StackRing<T, N> {
@@ -200,6 +452,287 @@ impl<T, const N: usize> Default for StackRing<T, N> {
     }
 }
 
+/// [`StackRing`] with `AtomicU32` cursors: half the control-word
+/// footprint and native word size on 32-bit targets. The free-running
+/// wraparound math is identical to the 64-bit version and stays correct
+/// for any `N <= u32::MAX` power of two. Embedded and cache-tight use
+/// cases; everything else should prefer `StackRing`.
+#[repr(C)]
+pub struct StackRing32<T, const N: usize> {
+    // === Producer hot path (cache line 1) ===
+    tail: AtomicU32,
+    cached_head: UnsafeCell<u32>,
+
+    // === Consumer hot path (cache line 2) ===
+    head: CacheLinePadded<AtomicU32>,
+    cached_tail: UnsafeCell<u32>,
+
+    // === Cold state ===
+    closed: AtomicBool,
+
+    // === Buffer (inline, no pointer indirection) ===
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+// SAFETY: same reasoning as StackRing — see the comment there.
+unsafe impl<T: Send, const N: usize> Send for StackRing32<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Sync for StackRing32<T, N> {}
+
+impl<T, const N: usize> StackRing32<T, N> {
+    const MASK: usize = N - 1;
+
+    /// Create a new ring; `N` must be a power of two representable in
+    /// the `u32` cursor.
+    pub const fn new() -> Self {
+        assert!(N > 0 && (N & (N - 1)) == 0, "N must be a power of 2");
+        assert!(N <= u32::MAX as usize, "N must fit the u32 cursor");
+
+        Self {
+            tail: AtomicU32::new(0),
+            cached_head: UnsafeCell::new(0),
+            head: CacheLinePadded(AtomicU32::new(0)),
+            cached_tail: UnsafeCell::new(0),
+            closed: AtomicBool::new(false),
+            // SAFETY: MaybeUninit doesn't require initialization
+            buffer: unsafe { MaybeUninit::uninit().assume_init() },
+        }
+    }
+
+    /// Reserve space for writing n elements; see [`StackRing::reserve`].
+    #[inline(always)]
+    pub unsafe fn reserve(&self, n: usize) -> Option<(*mut T, usize)> {
+        debug_assert!(n <= N, "reserve({}) can never succeed on a {}-slot ring", n, N);
+        if n > N {
+            return None;
+        }
+
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        let cached_head_ptr = self.cached_head.get();
+        let mut head = *cached_head_ptr;
+
+        let used = tail.wrapping_sub(head);
+        let mut free = (N as u32).wrapping_sub(used);
+
+        if free < (n as u32) {
+            head = self.head.load(Ordering::Acquire);
+            *cached_head_ptr = head;
+            let used = tail.wrapping_sub(head);
+            free = (N as u32).wrapping_sub(used);
+
+            if free < (n as u32) {
+                return None;
+            }
+        }
+
+        let idx = (tail as usize) & Self::MASK;
+        let contiguous = n.min(N - idx);
+
+        let ptr = (*self.buffer.as_ptr().add(idx)).get() as *mut T;
+        Some((ptr, contiguous))
+    }
+
+    /// Commit n elements that were written.
+    #[inline(always)]
+    pub fn commit(&self, n: usize) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        self.tail
+            .store(tail.wrapping_add(n as u32), Ordering::Release);
+    }
+
+    /// Peek at available data for reading; see [`StackRing::peek`].
+    #[inline(always)]
+    pub unsafe fn peek(&self) -> (*const T, usize) {
+        let head = self.head.load(Ordering::Relaxed);
+
+        let cached_tail_ptr = self.cached_tail.get();
+        let mut tail = *cached_tail_ptr;
+
+        if head == tail {
+            tail = self.tail.load(Ordering::Acquire);
+            *cached_tail_ptr = tail;
+            if head == tail {
+                return (std::ptr::null(), 0);
+            }
+        }
+
+        let idx = (head as usize) & Self::MASK;
+        let avail = tail.wrapping_sub(head) as usize;
+        let contiguous = avail.min(N - idx);
+
+        let ptr = (*self.buffer.as_ptr().add(idx)).get() as *const T;
+        (ptr, contiguous)
+    }
+
+    /// Advance the read pointer by n elements.
+    #[inline(always)]
+    pub fn advance(&self, n: usize) {
+        let head = self.head.load(Ordering::Relaxed);
+        self.head
+            .store(head.wrapping_add(n as u32), Ordering::Release);
+    }
+
+    /// Check if the ring is closed.
+    #[inline(always)]
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Check if the ring is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.tail.load(Ordering::Relaxed) == self.head.load(Ordering::Relaxed)
+    }
+
+    /// Close the ring (signals consumers).
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+}
+
+impl<T, const N: usize> Default for StackRing32<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// [`StackRing`] without the cache-line padding: the control words sit
+/// packed ahead of the buffer, so a tiny ring (N=8 of `u32`, say) is no
+/// longer dwarfed by 128-byte alignment gaps. This deliberately gives
+/// up the anti-false-sharing layout — producer and consumer cursors
+/// share a line — so it's for single-threaded or single-core embedded
+/// use where coherence traffic doesn't exist; cross-core SPSC should
+/// stay on `StackRing`.
+#[repr(C)]
+pub struct StackRingCompact<T, const N: usize> {
+    tail: AtomicU64,
+    head: AtomicU64,
+    cached_head: UnsafeCell<u64>,
+    cached_tail: UnsafeCell<u64>,
+    closed: AtomicBool,
+    buffer: [UnsafeCell<MaybeUninit<T>>; N],
+}
+
+// SAFETY: same reasoning as StackRing — see the comment there.
+unsafe impl<T: Send, const N: usize> Send for StackRingCompact<T, N> {}
+unsafe impl<T: Send + Sync, const N: usize> Sync for StackRingCompact<T, N> {}
+
+impl<T, const N: usize> StackRingCompact<T, N> {
+    const MASK: usize = N - 1;
+
+    /// Create a new compact ring; `N` must be a power of two.
+    pub const fn new() -> Self {
+        assert!(N > 0 && (N & (N - 1)) == 0, "N must be a power of 2");
+
+        Self {
+            tail: AtomicU64::new(0),
+            head: AtomicU64::new(0),
+            cached_head: UnsafeCell::new(0),
+            cached_tail: UnsafeCell::new(0),
+            closed: AtomicBool::new(false),
+            // SAFETY: MaybeUninit doesn't require initialization
+            buffer: unsafe { MaybeUninit::uninit().assume_init() },
+        }
+    }
+
+    /// Reserve space for writing n elements; see [`StackRing::reserve`].
+    #[inline(always)]
+    pub unsafe fn reserve(&self, n: usize) -> Option<(*mut T, usize)> {
+        debug_assert!(n <= N, "reserve({}) can never succeed on a {}-slot ring", n, N);
+        if n > N {
+            return None;
+        }
+
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        let cached_head_ptr = self.cached_head.get();
+        let mut head = *cached_head_ptr;
+
+        let used = tail.wrapping_sub(head);
+        let mut free = (N as u64).wrapping_sub(used);
+
+        if free < (n as u64) {
+            head = self.head.load(Ordering::Acquire);
+            *cached_head_ptr = head;
+            let used = tail.wrapping_sub(head);
+            free = (N as u64).wrapping_sub(used);
+
+            if free < (n as u64) {
+                return None;
+            }
+        }
+
+        let idx = (tail as usize) & Self::MASK;
+        let contiguous = n.min(N - idx);
+
+        let ptr = (*self.buffer.as_ptr().add(idx)).get() as *mut T;
+        Some((ptr, contiguous))
+    }
+
+    /// Commit n elements that were written.
+    #[inline(always)]
+    pub fn commit(&self, n: usize) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        self.tail
+            .store(tail.wrapping_add(n as u64), Ordering::Release);
+    }
+
+    /// Peek at available data for reading; see [`StackRing::peek`].
+    #[inline(always)]
+    pub unsafe fn peek(&self) -> (*const T, usize) {
+        let head = self.head.load(Ordering::Relaxed);
+
+        let cached_tail_ptr = self.cached_tail.get();
+        let mut tail = *cached_tail_ptr;
+
+        if head == tail {
+            tail = self.tail.load(Ordering::Acquire);
+            *cached_tail_ptr = tail;
+            if head == tail {
+                return (std::ptr::null(), 0);
+            }
+        }
+
+        let idx = (head as usize) & Self::MASK;
+        let avail = tail.wrapping_sub(head) as usize;
+        let contiguous = avail.min(N - idx);
+
+        let ptr = (*self.buffer.as_ptr().add(idx)).get() as *const T;
+        (ptr, contiguous)
+    }
+
+    /// Advance the read pointer by n elements.
+    #[inline(always)]
+    pub fn advance(&self, n: usize) {
+        let head = self.head.load(Ordering::Relaxed);
+        self.head
+            .store(head.wrapping_add(n as u64), Ordering::Release);
+    }
+
+    /// Check if the ring is closed.
+    #[inline(always)]
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Check if the ring is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.tail.load(Ordering::Relaxed) == self.head.load(Ordering::Relaxed)
+    }
+
+    /// Close the ring (signals consumers).
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+    }
+}
+
+impl<T, const N: usize> Default for StackRingCompact<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,6 +766,242 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_peek_slice() {
+        let ring: StackRing<u32, 8> = StackRing::new();
+
+        unsafe {
+            assert!(ring.peek_slice().is_empty());
+
+            for i in 0..3 {
+                let (ptr, _) = ring.reserve(1).unwrap();
+                *ptr = i;
+                ring.commit(1);
+            }
+
+            assert_eq!(ring.peek_slice(), &[0, 1, 2]);
+            ring.advance(2);
+            assert_eq!(ring.peek_slice(), &[2]);
+        }
+    }
+
+    #[test]
+    fn test_stack_ring32_wraparound() {
+        let ring: StackRing32<u32, 4> = StackRing32::new();
+        unsafe {
+            // Enough traffic to wrap the index mask several times
+            for i in 0..32u32 {
+                let (ptr, len) = ring.reserve(1).unwrap();
+                assert_eq!(len, 1);
+                *ptr = i;
+                ring.commit(1);
+
+                let (ptr, len) = ring.peek();
+                assert_eq!(len, 1);
+                assert_eq!(*ptr, i);
+                ring.advance(1);
+            }
+            assert!(ring.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_drop_releases_live_elements() {
+        use std::sync::atomic::AtomicUsize;
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct Counted;
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        {
+            let ring: StackRing<Counted, 8> = StackRing::new();
+            unsafe {
+                for _ in 0..3 {
+                    let (ptr, _) = ring.reserve(1).unwrap();
+                    ptr.write(Counted);
+                    ring.commit(1);
+                }
+                // Advanced-past slots count as consumed: their drop is
+                // the consumer's job, not the ring's.
+                let (ptr, _) = ring.peek();
+                std::ptr::drop_in_place(ptr as *mut Counted);
+                ring.advance(1);
+            }
+        }
+        // 1 dropped by the consumer + 2 still live when the ring dropped
+        assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_push_overwrite_keeps_latest() {
+        let ring: StackRing<u32, 4> = StackRing::new();
+        unsafe {
+            for i in 0..7u32 {
+                ring.push_overwrite(i);
+            }
+            // The three oldest were evicted; the latest 4 remain in order
+            let mut got = Vec::new();
+            ring.consume_batch(|v| got.push(*v));
+            assert_eq!(got, vec![3, 4, 5, 6]);
+        }
+    }
+
+    #[test]
+    fn test_push_overwrite_drops_evicted() {
+        use std::sync::atomic::AtomicUsize;
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct Counted(#[allow(dead_code)] u32);
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let ring: StackRing<Counted, 2> = StackRing::new();
+        unsafe {
+            for i in 0..5 {
+                ring.push_overwrite(Counted(i));
+            }
+        }
+        // 3 evictions while the ring stayed at 2 live elements
+        assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+        drop(ring);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn test_clear_drops_and_reopens() {
+        use std::sync::atomic::AtomicUsize;
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+        struct Counted;
+        impl Drop for Counted {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let mut ring: StackRing<Counted, 8> = StackRing::new();
+        unsafe {
+            for _ in 0..3 {
+                let (ptr, _) = ring.reserve(1).unwrap();
+                ptr.write(Counted);
+                ring.commit(1);
+            }
+        }
+        ring.close();
+
+        ring.clear();
+        assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+        assert!(ring.is_empty());
+        assert!(!ring.is_closed());
+
+        // Usable again from slot zero
+        unsafe {
+            let (ptr, _) = ring.reserve(1).unwrap();
+            ptr.write(Counted);
+            ring.commit(1);
+        }
+        drop(ring);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_peek_into_wraps_without_advancing() {
+        let ring: StackRing<u32, 4> = StackRing::new();
+        unsafe {
+            for i in 0..3u32 {
+                let (ptr, _) = ring.reserve(1).unwrap();
+                *ptr = i;
+                ring.commit(1);
+            }
+
+            let mut scratch = [0u32; 4];
+            assert_eq!(ring.peek_into(&mut scratch), 3);
+            assert_eq!(&scratch[..3], &[0, 1, 2]);
+
+            // Accept only two of the three peeked items
+            ring.advance(2);
+            for i in 3..6u32 {
+                let (ptr, _) = ring.reserve(1).unwrap();
+                *ptr = i;
+                ring.commit(1);
+            }
+
+            // The cached tail still ends at the first batch; draining
+            // to it makes the next call refresh and see the rest
+            assert_eq!(ring.peek_into(&mut scratch), 1);
+            assert_eq!(scratch[0], 2);
+            ring.advance(1);
+
+            // Readable run now wraps: slot 3 first, then slots 0..2
+            assert_eq!(ring.peek_into(&mut scratch), 3);
+            assert_eq!(&scratch[..3], &[3, 4, 5]);
+
+            // Non-advancing: the same items again, clipped to dst
+            let mut two = [0u32; 2];
+            assert_eq!(ring.peek_into(&mut two), 2);
+            assert_eq!(two, [3, 4]);
+
+            ring.advance(3);
+            assert_eq!(ring.peek_into(&mut scratch), 0);
+        }
+    }
+
+    #[test]
+    fn test_reserve_pointer_alignment() {
+        #[repr(align(64))]
+        #[derive(Clone, Copy)]
+        struct Aligned64(#[allow(dead_code)] u8);
+
+        let ring: StackRing<Aligned64, 8> = StackRing::new();
+        unsafe {
+            // Every slot index hands out a 64-aligned pointer
+            for _ in 0..8 {
+                let (ptr, _) = ring.reserve(1).unwrap();
+                assert_eq!(ptr as usize % 64, 0);
+                ptr.write(Aligned64(0));
+                ring.commit(1);
+                ring.consume_batch(|_| {});
+            }
+            // The buffer base itself is cache-line aligned
+            let (ptr, _) = ring.reserve(1).unwrap();
+            assert_eq!(ptr as usize % 128, 0);
+        }
+    }
+
+    #[test]
+    fn test_compact_roundtrip_and_size() {
+        let ring: StackRingCompact<u32, 8> = StackRingCompact::new();
+        unsafe {
+            // Enough traffic to wrap the index mask
+            for i in 0..32u32 {
+                let (ptr, len) = ring.reserve(1).unwrap();
+                assert_eq!(len, 1);
+                *ptr = i;
+                ring.commit(1);
+
+                let (ptr, len) = ring.peek();
+                assert_eq!(len, 1);
+                assert_eq!(*ptr, i);
+                ring.advance(1);
+            }
+            assert!(ring.is_empty());
+        }
+
+        // The point of the type: no 128-byte padding blocks. The padded
+        // ring spends more on alignment than this whole struct.
+        assert!(
+            std::mem::size_of::<StackRingCompact<u32, 8>>()
+                < std::mem::size_of::<StackRing<u32, 8>>() / 2
+        );
+    }
+
     #[test]
     fn test_full_ring() {
         let ring: StackRing<u32, 4> = StackRing::new();
@@ -246,6 +1015,7 @@ mod tests {
             }
 
             // Should be full now
+            assert!(ring.is_full());
             assert!(ring.reserve(1).is_none());
 
             // Drain one
@@ -254,6 +1024,7 @@ mod tests {
             ring.advance(1);
 
             // Now we can write again
+            assert!(!ring.is_full());
             assert!(ring.reserve(1).is_some());
         }
     }
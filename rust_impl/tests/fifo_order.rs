@@ -0,0 +1,50 @@
+//! Locks in the core SPSC delivery guarantee everything else depends
+//! on: a real producer/consumer thread pair over a small ring (many
+//! wraparounds) delivers the exact sequence — no gaps, duplicates, or
+//! reorderings.
+
+use rust_impl::raw_arc::RawArc;
+use rust_impl::Ring;
+
+#[test]
+fn spsc_delivers_exact_sequence() {
+    const MSGS: u64 = 1_000_000;
+    // 256 slots: ~4000 full wraps over the run
+    let ring = RawArc::new(Ring::<u64>::new(8));
+
+    let producer_ring = ring.clone();
+    let producer = std::thread::spawn(move || {
+        let mut sent = 0u64;
+        while sent < MSGS {
+            unsafe {
+                if let Some(r) = producer_ring.reserve(1) {
+                    *(r.ptr as *mut u64) = sent;
+                    producer_ring.commit(1);
+                    sent += 1;
+                } else {
+                    std::hint::spin_loop();
+                }
+            }
+        }
+        producer_ring.close();
+    });
+
+    let mut expected = 0u64;
+    loop {
+        let n = unsafe {
+            ring.consume_batch(|v| {
+                assert_eq!(*v, expected, "out-of-order or duplicated message");
+                expected += 1;
+            })
+        };
+        if n == 0 {
+            if ring.is_closed() && ring.is_empty() {
+                break;
+            }
+            std::hint::spin_loop();
+        }
+    }
+    assert_eq!(expected, MSGS, "missing messages");
+
+    producer.join().unwrap();
+}
@@ -0,0 +1,62 @@
+//! Realistic-scale data-race check for the unsafe atomics.
+//!
+//! Runs under plain `cargo test` as a smoke test, but its purpose is a
+//! ThreadSanitizer pass, which complements exhaustive small-model
+//! checkers with a race detector at real message volumes:
+//!
+//! ```text
+//! RUSTFLAGS="-Z sanitizer=thread" cargo +nightly test -Z build-std \
+//!     --target x86_64-unknown-linux-gnu --test tsan_stress
+//! ```
+//!
+//! TSan instruments every memory access, so a missing Acquire/Release
+//! pairing in reserve/commit/peek/advance shows up as a reported race
+//! rather than a once-a-week corruption.
+
+use rust_impl::raw_arc::RawArc;
+use rust_impl::Ring;
+
+#[test]
+fn producer_consumer_stress() {
+    const MSGS: u64 = 300_000;
+    const BATCH: usize = 64;
+
+    let ring = RawArc::new(Ring::<u64>::new(10));
+
+    let producer_ring = ring.clone();
+    let producer = std::thread::spawn(move || {
+        let mut sent = 0u64;
+        while sent < MSGS {
+            let want = (BATCH as u64).min(MSGS - sent) as usize;
+            unsafe {
+                if let Some(r) = producer_ring.reserve(want) {
+                    for j in 0..r.len {
+                        *(r.ptr as *mut u64).add(j) = sent + j as u64;
+                    }
+                    producer_ring.commit(r.len);
+                    sent += r.len as u64;
+                } else {
+                    std::hint::spin_loop();
+                }
+            }
+        }
+        producer_ring.close();
+    });
+
+    let mut received = 0u64;
+    let mut sum = 0u64;
+    loop {
+        let n = unsafe { ring.consume_batch(|v| sum += *v) };
+        if n > 0 {
+            received += n as u64;
+        } else if ring.is_closed() && ring.is_empty() {
+            break;
+        } else {
+            std::hint::spin_loop();
+        }
+    }
+
+    producer.join().unwrap();
+    assert_eq!(received, MSGS);
+    assert_eq!(sum, MSGS * (MSGS - 1) / 2);
+}
@@ -0,0 +1,356 @@
+//! RingMPSC - Reusable Benchmark Driver
+//!
+//! The machinery behind the benchmark binary, exposed as a library so
+//! parameter sweeps and CI throughput assertions can be scripted instead
+//! of eyeballing the table output.
+
+const std = @import("std");
+const ringmpsc = @import("channel");
+
+/// Per-run parameters (the comptime channel shape lives in `Bench`).
+pub const RunConfig = struct {
+    /// Number of producer/consumer pairs
+    producers: usize,
+    /// Messages per producer
+    msgs: u64 = 500_000_000,
+    /// Reservation batch size for amortizing atomic ops
+    batch: usize = 32768,
+    /// Pin threads to CPUs (producers first, consumers after)
+    pinned: bool = true,
+    /// Timed repetitions for the stddev estimate
+    reps: usize = 1,
+    /// In `runLatency`, timestamp every 2^n-th message (others carry 0)
+    latency_sample_shift: u6 = 10,
+    /// Simulated per-message consumer work in nanoseconds (busy-spin in
+    /// the handler). Zero keeps the flat-out drain; nonzero models a
+    /// loaded consumer, which is what production ring sizing should use —
+    /// the flat-out numbers keep the ring L1-resident and flatter
+    /// throughput considerably.
+    work_ns: u64 = 0,
+};
+
+pub const RunResult = struct {
+    /// Mean throughput in billion messages per second
+    throughput: f64,
+    /// Standard deviation across repetitions (0 for reps == 1)
+    stddev: f64,
+    /// Threads that could not be pinned (max across repetitions);
+    /// nonzero means the numbers should be labeled "unpinned"
+    unpinned: usize = 0,
+};
+
+/// End-to-end latency percentiles in nanoseconds.
+pub const LatencyResult = struct {
+    p50: u64,
+    p99: u64,
+    p999: u64,
+    samples: u64,
+};
+
+/// HDR-style histogram: power-of-two buckets with 16 linear sub-buckets,
+/// giving ~6% relative precision over the full u64 nanosecond range.
+pub const LatencyHistogram = struct {
+    const SUB_BITS: u6 = 4;
+    const SUB: usize = 1 << SUB_BITS;
+    const SLOTS: usize = 64 * SUB;
+
+    counts: [SLOTS]u64 = [_]u64{0} ** SLOTS,
+    total: u64 = 0,
+
+    pub fn record(self: *LatencyHistogram, ns: u64) void {
+        self.counts[index(ns)] += 1;
+        self.total += 1;
+    }
+
+    pub fn merge(self: *LatencyHistogram, other: *const LatencyHistogram) void {
+        for (&self.counts, other.counts) |*c, o| c.* += o;
+        self.total += other.total;
+    }
+
+    /// Lower bound of the bucket containing the q-th quantile (0 < q <= 1).
+    pub fn percentile(self: *const LatencyHistogram, q: f64) u64 {
+        if (self.total == 0) return 0;
+        const rank: u64 = @intFromFloat(@ceil(q * @as(f64, @floatFromInt(self.total))));
+        var seen: u64 = 0;
+        for (self.counts, 0..) |c, i| {
+            seen += c;
+            if (seen >= rank) return valueAt(i);
+        }
+        return valueAt(SLOTS - 1);
+    }
+
+    fn index(ns: u64) usize {
+        if (ns < SUB) return @intCast(ns);
+        const msb: u6 = @intCast(63 - @clz(ns));
+        const sub: usize = @intCast((ns >> (msb - SUB_BITS)) & (SUB - 1));
+        return (@as(usize, msb) - SUB_BITS + 1) * SUB + sub;
+    }
+
+    fn valueAt(i: usize) u64 {
+        if (i < SUB) return @intCast(i);
+        const msb: u6 = @intCast(i / SUB - 1 + SUB_BITS);
+        const sub: u64 = @intCast(i % SUB);
+        return (@as(u64, 1) << msb) | (sub << (msb - SUB_BITS));
+    }
+};
+
+/// Benchmark driver for a channel of the given element type and comptime
+/// config. Cache behavior differs dramatically by payload size, so measure
+/// with your actual message type, not just u32.
+pub fn Bench(comptime T: type, comptime config: ringmpsc.Config) type {
+    const ChannelType = ringmpsc.Channel(T, config);
+    const RingType = ringmpsc.Ring(T, config);
+
+    return struct {
+        /// Write pattern per slot: integer types carry the sequence number,
+        /// everything else is zero-filled (throughput is what's measured).
+        inline fn pattern(seq: u64) T {
+            return switch (@typeInfo(T)) {
+                .int => |info| if (info.bits <= 64) @truncate(seq) else @intCast(seq),
+                else => std.mem.zeroes(T),
+            };
+        }
+
+        // No-op consumer handler (compiler optimizes away the loop body)
+        const NoopHandler = struct {
+            pub fn process(_: NoopHandler, _: *const T) void {}
+        };
+
+        // Handler modeling real consumer load: burns `work_ns` per item so
+        // the measurement includes queue growth under a busy consumer
+        const WorkHandler = struct {
+            work_ns: u64,
+            pub fn process(self: WorkHandler, _: *const T) void {
+                busySpin(self.work_ns);
+            }
+        };
+
+        // Calibration-free busy wait: re-reads the clock until `ns` has
+        // elapsed. Precision is clock-read granularity (tens of ns), which
+        // is fine for modeling work costs in that range and above.
+        inline fn busySpin(ns: u64) void {
+            const start = std.time.Instant.now() catch return;
+            while (true) {
+                std.atomic.spinLoopHint();
+                const now = std.time.Instant.now() catch return;
+                if (now.since(start) >= ns) return;
+            }
+        }
+
+        /// Run the benchmark, repeating `rc.reps` times.
+        pub fn run(rc: RunConfig) !RunResult {
+            var sum: f64 = 0;
+            var sum_sq: f64 = 0;
+            var unpinned: usize = 0;
+            for (0..rc.reps) |_| {
+                const once = try runOnce(rc);
+                sum += once.rate;
+                sum_sq += once.rate * once.rate;
+                unpinned = @max(unpinned, once.unpinned);
+            }
+            const n = @as(f64, @floatFromInt(rc.reps));
+            const mean = sum / n;
+            const variance = @max(sum_sq / n - mean * mean, 0);
+            return .{ .throughput = mean, .stddev = @sqrt(variance), .unpinned = unpinned };
+        }
+
+        pub const RunOnce = struct {
+            /// Billion messages per second
+            rate: f64,
+            /// Threads that could not be pinned this run
+            unpinned: usize,
+        };
+
+        /// Single timed run.
+        pub fn runOnce(rc: RunConfig) !RunOnce {
+            std.debug.assert(rc.producers <= config.max_producers);
+
+            var channel: ChannelType = .{};
+
+            var producer_threads: [config.max_producers]std.Thread = undefined;
+            var consumer_threads: [config.max_producers]std.Thread = undefined;
+            var producers: [config.max_producers]ChannelType.Producer = undefined;
+            var counts_c: [config.max_producers]std.atomic.Value(u64) =
+                [_]std.atomic.Value(u64){std.atomic.Value(u64).init(0)} ** config.max_producers;
+            var unpinned = std.atomic.Value(usize).init(0);
+
+            for (0..rc.producers) |i| producers[i] = channel.register() catch unreachable;
+
+            const t0 = std.time.Instant.now() catch unreachable;
+
+            // Consumers pinned to CPUs after the producers
+            for (0..rc.producers) |i| {
+                consumer_threads[i] = try std.Thread.spawn(.{}, consumerLoop, .{ &channel.rings[i], rc, &counts_c[i], rc.producers + i, &unpinned });
+            }
+
+            for (0..rc.producers) |i| {
+                producer_threads[i] = try std.Thread.spawn(.{}, producerLoop, .{ &producers[i], rc, i, &unpinned });
+            }
+
+            for (0..rc.producers) |i| producer_threads[i].join();
+
+            // Close rings to signal consumers
+            for (0..rc.producers) |i| channel.rings[i].close();
+
+            for (0..rc.producers) |i| consumer_threads[i].join();
+
+            const ns = (std.time.Instant.now() catch unreachable).since(t0);
+
+            var count_c: u64 = 0;
+            for (0..rc.producers) |i| count_c += counts_c[i].load(.acquire);
+
+            return .{
+                .rate = @as(f64, @floatFromInt(count_c)) / @as(f64, @floatFromInt(ns)),
+                .unpinned = unpinned.load(.acquire),
+            };
+        }
+
+        // Latency runs use a dedicated u64 channel: sampled messages carry a
+        // nanosecond offset from a shared base instant, the rest carry 0.
+        const LatChannel = ringmpsc.Channel(u64, config);
+
+        const LatencyHandler = struct {
+            base: std.time.Instant,
+            hist: *LatencyHistogram,
+
+            pub fn process(self: @This(), item: *const u64) void {
+                if (item.* == 0) return;
+                const now = std.time.Instant.now() catch return;
+                const ns = now.since(self.base);
+                if (ns > item.*) self.hist.record(ns - item.*);
+            }
+        };
+
+        /// Measure end-to-end latency: every 2^latency_sample_shift-th
+        /// message is timestamped at produce time and the delta recorded at
+        /// consume time into an HDR-style histogram.
+        pub fn runLatency(rc: RunConfig) !LatencyResult {
+            std.debug.assert(rc.producers <= config.max_producers);
+
+            var channel: LatChannel = .{};
+            const base = std.time.Instant.now() catch unreachable;
+
+            var producer_threads: [config.max_producers]std.Thread = undefined;
+            var consumer_threads: [config.max_producers]std.Thread = undefined;
+            var producers: [config.max_producers]LatChannel.Producer = undefined;
+            var hists: [config.max_producers]LatencyHistogram = [_]LatencyHistogram{.{}} ** config.max_producers;
+
+            for (0..rc.producers) |i| producers[i] = channel.register() catch unreachable;
+
+            for (0..rc.producers) |i| {
+                consumer_threads[i] = try std.Thread.spawn(.{}, latencyConsumerLoop, .{ &channel.rings[i], rc, base, &hists[i], rc.producers + i });
+            }
+
+            for (0..rc.producers) |i| {
+                producer_threads[i] = try std.Thread.spawn(.{}, latencyProducerLoop, .{ &producers[i], rc, base, i });
+            }
+
+            for (0..rc.producers) |i| producer_threads[i].join();
+            for (0..rc.producers) |i| channel.rings[i].close();
+            for (0..rc.producers) |i| consumer_threads[i].join();
+
+            var merged = LatencyHistogram{};
+            for (0..rc.producers) |i| merged.merge(&hists[i]);
+
+            return .{
+                .p50 = merged.percentile(0.50),
+                .p99 = merged.percentile(0.99),
+                .p999 = merged.percentile(0.999),
+                .samples = merged.total,
+            };
+        }
+
+        fn latencyProducerLoop(p: *LatChannel.Producer, rc: RunConfig, base: std.time.Instant, cpu: usize) void {
+            if (rc.pinned) _ = pin(cpu);
+            const sample_mask = (@as(u64, 1) << rc.latency_sample_shift) - 1;
+            var sent: u64 = 0;
+
+            while (sent < rc.msgs) {
+                const want = @min(rc.batch, rc.msgs - sent);
+                if (p.reserve(want)) |r| {
+                    for (r.slice, 0..) |*slot, i| {
+                        if ((sent + i) & sample_mask == 0) {
+                            const now = std.time.Instant.now() catch unreachable;
+                            slot.* = now.since(base);
+                        } else {
+                            slot.* = 0;
+                        }
+                    }
+                    p.commit(r.slice.len);
+                    sent += r.slice.len;
+                } else {
+                    std.atomic.spinLoopHint();
+                }
+            }
+        }
+
+        fn latencyConsumerLoop(ring: *ringmpsc.Ring(u64, config), rc: RunConfig, base: std.time.Instant, hist: *LatencyHistogram, cpu: usize) void {
+            if (rc.pinned) _ = pin(cpu);
+
+            while (true) {
+                const consumed = ring.consumeBatch(LatencyHandler{ .base = base, .hist = hist });
+                if (consumed == 0) {
+                    if (ring.isClosed() and ring.isEmpty()) break;
+                    std.atomic.spinLoopHint();
+                }
+            }
+        }
+
+        fn producerLoop(p: *ChannelType.Producer, rc: RunConfig, cpu: usize, unpinned: *std.atomic.Value(usize)) void {
+            if (rc.pinned and !pin(cpu)) _ = unpinned.fetchAdd(1, .monotonic);
+            var sent: u64 = 0;
+
+            while (sent < rc.msgs) {
+                const want = @min(rc.batch, rc.msgs - sent);
+                if (p.reserve(want)) |r| {
+                    // Write pattern (optimized 4-way unroll)
+                    var i: usize = 0;
+                    while (i + 4 <= r.slice.len) : (i += 4) {
+                        r.slice[i] = pattern(sent + i);
+                        r.slice[i + 1] = pattern(sent + i + 1);
+                        r.slice[i + 2] = pattern(sent + i + 2);
+                        r.slice[i + 3] = pattern(sent + i + 3);
+                    }
+                    while (i < r.slice.len) : (i += 1) {
+                        r.slice[i] = pattern(sent + i);
+                    }
+                    p.commit(r.slice.len);
+                    sent += r.slice.len;
+                } else {
+                    std.atomic.spinLoopHint();
+                }
+            }
+        }
+
+        fn consumerLoop(ring: *RingType, rc: RunConfig, count_out: *std.atomic.Value(u64), cpu: usize, unpinned: *std.atomic.Value(usize)) void {
+            if (rc.pinned and !pin(cpu)) _ = unpinned.fetchAdd(1, .monotonic);
+            var count: u64 = 0;
+
+            while (true) {
+                const consumed = if (rc.work_ns == 0)
+                    ring.consumeBatch(NoopHandler{})
+                else
+                    ring.consumeBatch(WorkHandler{ .work_ns = rc.work_ns });
+                count += consumed;
+                if (consumed == 0) {
+                    if (ring.isClosed() and ring.isEmpty()) break;
+                    std.atomic.spinLoopHint();
+                }
+            }
+
+            count_out.store(count, .release);
+        }
+    };
+}
+
+/// Pin the calling thread to `cpu`. Returns false (leaving the thread
+/// unpinned) when the CPU doesn't exist, instead of silently wrapping and
+/// doubling threads up on cores — so results can be labeled honestly.
+pub fn pin(cpu: usize) bool {
+    const max = std.Thread.getCpuCount() catch return false;
+    if (cpu >= max) return false;
+    var set = std.mem.zeroes(std.os.linux.cpu_set_t);
+    set[cpu / 64] |= @as(u64, 1) << @as(u6, @intCast(cpu % 64));
+    _ = std.os.linux.sched_setaffinity(0, &set) catch return false;
+    return true;
+}
@@ -18,13 +18,71 @@ const builtin = @import("builtin");
 // CONFIGURATION
 // ============================================================================
 
+/// Channel configuration. A plain value type: copy a shared template and
+/// tweak fields per channel, e.g.
+/// `var cfg = default_config; cfg.ring_bits = 18;`
 pub const Config = struct {
-    /// Ring buffer size as power of 2 (default: 16 = 64K slots)
+    /// Ring buffer size as power of 2 (default: 16 = 64K slots).
+    /// `0` is a supported degenerate case: a single-slot mailbox for
+    /// one-at-a-time handoff.
     ring_bits: u6 = 16,
     /// Maximum number of producers
     max_producers: usize = 16,
     /// Enable metrics collection (slight overhead)
     enable_metrics: bool = false,
+    /// Use 32-bit head/tail cursors: halves the control-word footprint and
+    /// matches native word size on 32-bit targets. Requires ring_bits < 32.
+    compact_cursors: bool = false,
+    /// Record a commit timestamp per slot so consumers can read each
+    /// item's time-in-queue (see `consumeBatchTimed`). Costs a clock read
+    /// per commit and a parallel timestamp array, so it's off by default —
+    /// enable it on channels with a latency SLA to watch.
+    track_dwell: bool = false,
+    /// Pad the producer/consumer hot fields out to their own 128-byte
+    /// cache lines (and the buffer to 64). Leave on for any concurrent
+    /// use. Turning it off collapses the ring to natural alignment for
+    /// memory-constrained single-threaded deployments — a tiny ring's
+    /// padding otherwise dwarfs its buffer — at the cost of the
+    /// false-sharing guarantee.
+    pad_cache_lines: bool = true,
+
+    /// Derive a config sized for at least `min_slots` (rounded up to the
+    /// next power of two), for callers who'd rather not think in exponents:
+    /// `Ring(u64, default_config.withCapacity(10_000))` gives 16K slots.
+    pub fn withCapacity(base: Config, min_slots: usize) Config {
+        var cfg = base;
+        cfg.ring_bits = @intCast(std.math.log2_int_ceil(usize, @max(min_slots, 1)));
+        return cfg;
+    }
+
+    /// Field-wise equality, for pooling layers that dedup channel
+    /// creation by config. Config is plain data (no pointers), so
+    /// `std.AutoHashMap(Config, ...)` also works on it directly; `eql`
+    /// and `hash` are for hand-rolled contexts and table-free checks.
+    pub fn eql(self: Config, other: Config) bool {
+        return std.meta.eql(self, other);
+    }
+
+    /// Hash over all fields, consistent with `eql`.
+    pub fn hash(self: Config) u64 {
+        var h = std.hash.Wyhash.init(0);
+        std.hash.autoHash(&h, self);
+        return h.final();
+    }
+
+    /// Central comptime validation. Every generic type in this file runs
+    /// it, so an invalid combination fails at the type's declaration with
+    /// one message instead of each type re-checking its own subset.
+    /// (Struct literals with defaults are this codebase's builder; the
+    /// checks a builder's `build()` would run live here.)
+    pub fn validate(comptime self: Config) void {
+        if (self.compact_cursors and self.ring_bits >= 32) {
+            @compileError("compact_cursors requires ring_bits < 32");
+        }
+        if (self.max_producers == 0) {
+            @compileError("max_producers must be at least 1");
+        }
+    }
 };
 
 pub const default_config = Config{};
@@ -71,6 +129,93 @@ pub const Backoff = struct {
     }
 };
 
+// ============================================================================
+// CYCLE TIMESTAMPS - per-message clocks cheap enough for the hot path
+// ============================================================================
+
+/// Raw cycle counter (x86_64 `rdtsc`), ~nanosecond overhead versus the
+/// syscall-level `Instant.now()` — the clock to stamp per-message events
+/// with. Raw TSC reads can reorder with surrounding instructions; use
+/// `rdtscp` when the stamp must not drift into the measured region. On
+/// non-x86 targets this falls back to the OS monotonic clock, so the
+/// units are nanoseconds there instead of cycles — always convert
+/// through `cyclesToNanos` with a calibrated rate rather than assuming.
+pub inline fn rdtsc() u64 {
+    return switch (builtin.cpu.arch) {
+        .x86_64 => asm volatile (
+            \\rdtsc
+            \\shlq $32, %%rdx
+            \\orq %%rdx, %%rax
+            : [ret] "={rax}" (-> u64),
+            :
+            : "rdx"
+        ),
+        else => @intCast(std.time.nanoTimestamp()),
+    };
+}
+
+/// Partially-serializing counterpart (`rdtscp`): waits for earlier
+/// instructions to retire before reading, so the stamp cannot land
+/// before the work it is supposed to follow. A shade slower than
+/// `rdtsc`; use it on the closing edge of a measured interval.
+pub inline fn rdtscp() u64 {
+    return switch (builtin.cpu.arch) {
+        .x86_64 => asm volatile (
+            \\rdtscp
+            \\shlq $32, %%rdx
+            \\orq %%rdx, %%rax
+            : [ret] "={rax}" (-> u64),
+            :
+            : "rdx", "rcx"
+        ),
+        else => rdtsc(),
+    };
+}
+
+/// Convert a cycle delta to nanoseconds at the given TSC rate. Widens
+/// internally, so large deltas don't overflow the multiply.
+pub inline fn cyclesToNanos(cycles: u64, tsc_hz: u64) u64 {
+    return @intCast(@as(u128, cycles) * std.time.ns_per_s / @max(tsc_hz, 1));
+}
+
+/// Measure the TSC rate against the OS monotonic clock over roughly
+/// `sample_ns`. Run once at startup (a few milliseconds gives ppm-level
+/// accuracy on an invariant TSC) and feed the result to
+/// `cyclesToNanos`; on the non-x86 fallback this returns ~1 GHz since
+/// the "cycles" are already nanoseconds.
+pub fn calibrateTscHz(sample_ns: u64) u64 {
+    const t0 = std.time.Instant.now() catch unreachable;
+    const c0 = rdtsc();
+    while (true) {
+        const t1 = std.time.Instant.now() catch unreachable;
+        const elapsed = t1.since(t0);
+        if (elapsed >= sample_ns) {
+            const cycles = rdtscp() -% c0;
+            return @intCast(@as(u128, cycles) * std.time.ns_per_s / @max(elapsed, 1));
+        }
+        std.atomic.spinLoopHint();
+    }
+}
+
+// ============================================================================
+// PUBLISH/OBSERVE - the ring's ordering discipline, reusable
+// ============================================================================
+
+/// Release-store for hand-rolled side channels (notification words,
+/// sequence counters): everything this thread wrote before `publish` is
+/// visible to any thread that `observe`s the new value. This is the same
+/// edge the ring's commit rides on, named so ring-adjacent structures
+/// reuse the discipline instead of guessing at orderings.
+pub inline fn publish(comptime T: type, atomic: *std.atomic.Value(T), value: T) void {
+    atomic.store(value, .release);
+}
+
+/// Acquire-load counterpart of `publish`. After observing a published
+/// value, reads of whatever that value guards are well-defined.
+pub inline fn observe(comptime T: type, atomic: *const std.atomic.Value(T)) T {
+    return atomic.load(.acquire);
+}
+
 // ============================================================================
 // ZERO-COPY RESERVATION
 // ============================================================================
@@ -79,6 +224,17 @@ pub fn Reservation(comptime T: type) type {
     return struct {
         slice: []T,
         pos: u64,
+        /// What the caller asked for. `slice.len` can be smaller when the
+        /// grant was clipped at the wraparound boundary.
+        requested: usize,
+
+        /// False when the grant came back shorter than the request.
+        /// Commit `slice.len` in that case, not the original n — the ring
+        /// debug-asserts against the granted length, but in release an
+        /// over-commit silently publishes unwritten slots.
+        pub fn isComplete(self: @This()) bool {
+            return self.slice.len == self.requested;
+        }
     };
 }
 
@@ -92,48 +248,219 @@ pub const Metrics = struct {
     batches_sent: u64 = 0,
     batches_received: u64 = 0,
     reserve_spins: u64 = 0,
+    messages_dropped: u64 = 0,
+    /// Producer-side slow paths: reserve re-read head because the cached
+    /// value looked full. High rates mean the cached-cursor optimization
+    /// isn't helping (producer and consumer running lock-step).
+    producer_cache_refreshes: u64 = 0,
+    /// Consumer-side slow paths: readable re-read tail (cache said empty)
+    consumer_cache_refreshes: u64 = 0,
+};
+
+// ============================================================================
+// FULL-RING POLICY
+// ============================================================================
+
+/// What a producer does when the ring is full.
+pub const OnFull = enum {
+    /// Discard the new value (counted in `messages_dropped` when metrics on)
+    drop_new,
+    /// Retry with adaptive backoff until sent or the ring closes
+    block,
+    /// Give up immediately and report `.full`
+    fail,
+};
+
+pub const SendOutcome = enum { sent, dropped, full };
+
+// ============================================================================
+// MONITORING
+// ============================================================================
+
+/// One consistent view of a ring's state, for metrics exporters.
+pub const RingSnapshot = struct {
+    head: u64,
+    tail: u64,
+    len: usize,
+    closed: bool,
+};
+
+/// Stable-ABI, point-in-time snapshot of a ring's control state.
+///
+/// An `extern struct` with C layout so the copy can be handed across an
+/// FFI boundary (diagnostics, crash dumps, monitoring). It is a value
+/// snapshot, NOT a live control block: the cursors are plain `u64`s
+/// frozen at export time, and a C process cannot attach to one to follow
+/// or drive a ring. The live shared-memory control block lives on the
+/// Rust side (`rust_impl::ring_header::RingHeader`, mirrored by
+/// `include/ringmpsc.h`), which uses atomics for exactly that purpose.
+/// Field order, widths and the 128-byte cursor alignment are frozen —
+/// bump `HEADER_VERSION` if they ever change.
+pub const RingHeader = extern struct {
+    pub const HEADER_VERSION: u32 = 1;
+
+    /// Consumer cursor (free-running, wraps modulo 2^64).
+    head: u64 align(128),
+    /// Producer cursor (free-running, wraps modulo 2^64).
+    tail: u64 align(128),
+    /// Slot count of the ring the header describes.
+    capacity: u64,
+    /// Layout version, always `HEADER_VERSION`.
+    version: u32,
+    /// 0 = open, 1 = closed.
+    closed: u32,
 };
 
 // ============================================================================
 // SPSC RING BUFFER - The Core
 // ============================================================================
 
+/// SPSC ring buffer.
+///
+/// Rings are plain structs with default field values and no hidden
+/// allocation, so a file-scope `var ring: RingType = .{};` works as a
+/// static global — no heap, no leak tricks (the test executables rely
+/// on this).
+///
+/// Thread contract (what the Rust port encodes as Send/Sync bounds):
+/// exactly one thread owns the producer API (`reserve`/`commit`/`send`)
+/// and one thread the consumer API (`readable`/`advance`/`consumeBatch`).
+/// Elements cross the boundary by value; handler callbacks receive
+/// `*const T` on the consumer thread only, never a reference shared with
+/// the producer. `consumeShared` is the documented exception and relaxes
+/// the single-consumer half.
 pub fn Ring(comptime T: type, comptime config: Config) type {
     const CAPACITY = @as(usize, 1) << config.ring_bits;
     const MASK = CAPACITY - 1;
+    comptime config.validate();
+
+    // A deliberate message beats the arithmetic-overflow error the buffer
+    // declaration would otherwise die with: a service that picks ring_bits
+    // from config should learn *which* knob to turn, not that `[N]T` broke.
+    // Capped at maxInt(isize) — the largest object a single allocation (or
+    // pointer difference) can address.
+    comptime {
+        const bytes = std.math.mulWide(u64, CAPACITY, @sizeOf(T));
+        if (bytes > std.math.maxInt(isize)) {
+            @compileError(std.fmt.comptimePrint(
+                "ring buffer is {d} bytes ({d} slots x {d}-byte elements), past the isize limit: lower ring_bits or shrink T",
+                .{ bytes, CAPACITY, @sizeOf(T) },
+            ));
+        }
+    }
 
     return struct {
         const Self = @This();
 
+        /// Free-running cursor type; u32 when `compact_cursors` is set.
+        /// Wraparound math is unchanged as long as capacity fits the width.
+        pub const Cursor = if (config.compact_cursors) u32 else u64;
+
+        // Slots to prefetch ahead of the current position in batch drains
+        // (~one cache line of u64s; far enough to hide DRAM latency)
+        const PREFETCH_DISTANCE = 8;
+
+        const CANARY_ENABLED = builtin.mode == .Debug;
+        const CANARY: u8 = 0xA5;
+
+        // Alignments collapse to natural when padding is configured off
+        const HOT_ALIGN = if (config.pad_cache_lines) 128 else @alignOf(std.atomic.Value(Cursor));
+        const BUF_ALIGN = if (config.pad_cache_lines) 64 else @alignOf(T);
+
         // === PRODUCER HOT === (128-byte aligned to avoid prefetcher false sharing)
-        tail: std.atomic.Value(u64) align(128) = std.atomic.Value(u64).init(0),
-        cached_head: u64 = 0, // Producer's cached view of head
+        tail: std.atomic.Value(Cursor) align(HOT_ALIGN) = std.atomic.Value(Cursor).init(0),
+        cached_head: Cursor = 0, // Producer's cached view of head
+        reserved: usize = 0, // Granted length of the outstanding reservation (producer-local; commit debug-checks it)
 
         // === CONSUMER HOT === (separate 128-byte line)
-        head: std.atomic.Value(u64) align(128) = std.atomic.Value(u64).init(0),
-        cached_tail: u64 = 0, // Consumer's cached view of tail
+        head: std.atomic.Value(Cursor) align(HOT_ALIGN) = std.atomic.Value(Cursor).init(0),
+        cached_tail: Cursor = 0, // Consumer's cached view of tail
+        // Work-stealing claim cursor (consumeShared only): consumers CAS
+        // here to partition [head, tail); head itself advances only after
+        // a batch is fully processed, so the producer never sees claimed-
+        // but-unread slots as free space. Always >= head; equal outside
+        // consumeShared.
+        shared_claim: std.atomic.Value(Cursor) = std.atomic.Value(Cursor).init(0),
 
         // === COLD STATE === (rarely accessed)
-        active: std.atomic.Value(bool) align(128) = std.atomic.Value(bool).init(false),
+        active: std.atomic.Value(bool) align(HOT_ALIGN) = std.atomic.Value(bool).init(false),
         closed: std.atomic.Value(bool) = std.atomic.Value(bool).init(false),
         metrics: if (config.enable_metrics) Metrics else void =
             if (config.enable_metrics) .{} else {},
+        // Wall-clock nanosecond stamp of the last head advance (gated on
+        // metrics). Written by the consumer, read by a watchdog thread —
+        // hence atomic, and parked in cold state rather than on the
+        // consumer's hot line. 0 until the first advance.
+        last_consume_ns: if (config.enable_metrics) std.atomic.Value(u64) else void =
+            if (config.enable_metrics) std.atomic.Value(u64).init(0) else {},
 
         // === DATA BUFFER === (64-byte aligned for cache efficiency)
-        buffer: [CAPACITY]T align(64) = undefined,
+        buffer: [CAPACITY]T align(BUF_ALIGN) = undefined,
+
+        // Per-slot commit instants for dwell-time tracking (gated: zero
+        // bytes and zero work unless track_dwell is set)
+        timestamps: if (config.track_dwell) [CAPACITY]std.time.Instant else void =
+            if (config.track_dwell) undefined else {},
+
+        // Debug-only torn-read canary: commit stamps each published slot
+        // and the batch drains verify the stamp before reading, catching a
+        // consumer that reaches a slot ahead of the producer's commit — a
+        // contract violation that otherwise yields silent garbage for
+        // multi-word T. Zero cost in release builds.
+        canaries: if (CANARY_ENABLED) [CAPACITY]u8 else void =
+            if (CANARY_ENABLED) [_]u8{0} ** CAPACITY else {},
 
         // ---------------------------------------------------------------------
         // CONSTANTS
         // ---------------------------------------------------------------------
 
+        /// Slot count (`1 << ring_bits`). Callable at comptime, so
+        /// companion arrays can be sized off the ring:
+        /// `var seen: [RingType.capacity()]bool = undefined;`
         pub fn capacity() usize {
             return CAPACITY;
         }
 
+        /// Index mask (`capacity() - 1`). Comptime-callable like `capacity`.
         pub fn mask() usize {
             return MASK;
         }
 
+        /// Byte size of the data buffer alone (`capacity() * @sizeOf(T)`),
+        /// distinct from slot count — the number an `ftruncate` on a
+        /// shared-memory file needs. Comptime-callable.
+        pub fn capacityBytes() usize {
+            return CAPACITY * @sizeOf(T);
+        }
+
+        /// Byte size of the control block ahead of the buffer (cursors,
+        /// caches, flags, padding). `headerBytes() + capacityBytes()`
+        /// rounds up to `memoryFootprint()`.
+        pub fn headerBytes() usize {
+            return @offsetOf(Self, "buffer");
+        }
+
+        /// Real memory cost of one ring in bytes — buffer, cursors and the
+        /// 128-byte alignment padding. The buffer is embedded, so this is
+        /// just the struct size; comptime-callable for capacity planning.
+        pub fn memoryFootprint() usize {
+            return @sizeOf(Self);
+        }
+
+        /// Compile-time guard that the whole ring — cursors, padding and
+        /// buffer — fits a byte budget, e.g. an L1-resident ring:
+        /// `comptime RingType.assertFitsIn(32 * 1024);`
+        /// Catches an oversized `T` times a generous `ring_bits` at build
+        /// time instead of as cache thrashing in production.
+        pub fn assertFitsIn(comptime budget: usize) void {
+            if (@sizeOf(Self) > budget) {
+                @compileError(std.fmt.comptimePrint(
+                    "ring is {d} bytes, over the {d}-byte budget",
+                    .{ @sizeOf(Self), budget },
+                ));
+            }
+        }
+
         // ---------------------------------------------------------------------
         // STATUS
         // ---------------------------------------------------------------------
@@ -148,20 +475,66 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
             return self.tail.load(.monotonic) == self.head.load(.monotonic);
         }
 
+        /// True when no slot is free. Loads head with `.acquire` so a
+        /// producer polling for back-pressure gets a fresh answer rather
+        /// than its cached view.
         pub inline fn isFull(self: *const Self) bool {
-            return self.len() >= CAPACITY;
+            const t = self.tail.load(.monotonic);
+            const h = self.head.load(.acquire);
+            return t -% h >= CAPACITY;
         }
 
         pub inline fn isClosed(self: *const Self) bool {
             return self.closed.load(.acquire);
         }
 
+        /// Consistent `(head, tail, len, closed)` snapshot in one call.
+        /// Head is loaded before tail, so `len == tail -% head` can never
+        /// underflow within the snapshot — no nonsensical negative backlogs
+        /// in the monitoring output.
+        pub fn snapshot(self: *const Self) RingSnapshot {
+            const h = self.head.load(.acquire);
+            const t = self.tail.load(.acquire);
+            return .{
+                .head = h,
+                .tail = t,
+                .len = @intCast(t -% h),
+                .closed = self.closed.load(.acquire),
+            };
+        }
+
+        /// Fill a [RingHeader] snapshot from this ring's current state.
+        /// The result is a frozen copy for diagnostics/FFI hand-off; it
+        /// does not track the ring afterwards.
+        pub fn exportHeader(self: *const Self) RingHeader {
+            const s = self.snapshot();
+            return .{
+                .head = s.head,
+                .tail = s.tail,
+                .capacity = CAPACITY,
+                .version = RingHeader.HEADER_VERSION,
+                .closed = @intFromBool(s.closed),
+            };
+        }
+
         // ---------------------------------------------------------------------
         // PRODUCER API
         // ---------------------------------------------------------------------
 
         /// Reserve n slots for zero-copy writing. Returns null if full/closed.
+        /// `n > capacity()` can never succeed: it asserts in safe builds and
+        /// returns null in release, so callers don't spin forever on it.
+        ///
+        /// Alignment guarantee: the returned slice always starts at a
+        /// `@alignOf(T)`-aligned address, including for over-aligned T
+        /// (vectors, `align(N)` structs) — the buffer is a true `[N]T`
+        /// with at least the element's alignment, and slot strides are
+        /// multiples of `@sizeOf(T)`, so no cursor position can produce a
+        /// misaligned pointer. For alignment beyond the element's natural
+        /// one (e.g. a 64-byte SIMD store window over u8 slots), use
+        /// `reserveAligned`.
         pub inline fn reserve(self: *Self, n: usize) ?Reservation(T) {
+            std.debug.assert(n <= CAPACITY);
             if (n == 0 or n > CAPACITY) return null;
 
             const tail = self.tail.load(.monotonic);
@@ -174,15 +547,118 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
 
             // Slow path: refresh cache
             self.cached_head = self.head.load(.acquire);
+            if (config.enable_metrics) {
+                _ = @atomicRmw(u64, &self.metrics.producer_cache_refreshes, .Add, 1, .monotonic);
+            }
             space = CAPACITY -| (tail -% self.cached_head);
             if (space < n) return null;
 
             return self.makeReservation(tail, n);
         }
 
+        /// Why `reserve` failed, for retry loops that must not spin on a
+        /// failure no amount of retrying can fix.
+        pub const ReserveResult = union(enum) {
+            /// Reservation made (possibly clipped at the wrap).
+            granted: Reservation(T),
+            /// Transiently full: the consumer will make space — retry.
+            would_block,
+            /// The ring was closed: no retry will ever succeed.
+            closed,
+            /// `n > capacity()`: a programming error, not a queue state —
+            /// fail fast instead of hanging the producer forever.
+            too_large,
+        };
+
+        /// `reserve` with the failure modes told apart, so a producer's
+        /// retry loop can spin on `.would_block` and bail on the two
+        /// permanent cases. Prefer this over bare `reserve` in generic
+        /// code where n isn't statically known to fit: plain `reserve`
+        /// folds "never fits" into the same null as "full right now",
+        /// which turns an oversized request into a silent infinite loop
+        /// in release builds.
+        pub fn reserveChecked(self: *Self, n: usize) ReserveResult {
+            if (n > CAPACITY) return .too_large;
+            if (self.isClosed()) return .closed;
+            if (self.reserve(n)) |r| return .{ .granted = r };
+            return .would_block;
+        }
+
+        /// Both writable runs for an n-slot reservation in one call: the
+        /// pre-wrap and post-wrap slices (second empty when the request
+        /// doesn't cross the boundary). Null when fewer than n slots are
+        /// free. The write-side mirror of `peekBoth`: fill both parts with
+        /// plain slice indexing, then `commit(n)` — no dealing with a
+        /// clipped grant and no second reserve call at the wrap.
+        pub const WritableSegments = struct {
+            first: []T,
+            second: []T,
+        };
+
+        pub fn reserveBoth(self: *Self, n: usize) ?WritableSegments {
+            std.debug.assert(n <= CAPACITY);
+            if (n == 0 or n > CAPACITY) return null;
+
+            const tail = self.tail.load(.monotonic);
+            var space = CAPACITY -| (tail -% self.cached_head);
+            if (space < n) {
+                self.cached_head = self.head.load(.acquire);
+                if (config.enable_metrics) {
+                    _ = @atomicRmw(u64, &self.metrics.producer_cache_refreshes, .Add, 1, .monotonic);
+                }
+                space = CAPACITY -| (tail -% self.cached_head);
+                if (space < n) return null;
+            }
+
+            const idx: usize = @intCast(tail & MASK);
+            const first = @min(n, CAPACITY - idx);
+            self.reserved = n; // both parts together are the grant
+            return .{
+                .first = self.buffer[idx..][0..first],
+                .second = self.buffer[0 .. n - first],
+            };
+        }
+
+        /// Stateful retry helper for the full-ring busy-wait: `begin` once,
+        /// then call `retry` in the loop. Tail is captured once — only this
+        /// producer moves it — so each retry re-reads just the consumer's
+        /// head, instead of the tail + head pair a fresh `reserve` pays.
+        pub const ReserveAttempt = struct {
+            ring: *Self,
+            tail: Cursor,
+            n: usize,
+
+            /// One retry: a single `.acquire` head load. Null while still full.
+            pub fn retry(self: *const ReserveAttempt) ?Reservation(T) {
+                self.ring.cached_head = self.ring.head.load(.acquire);
+                if (config.enable_metrics) {
+                    _ = @atomicRmw(u64, &self.ring.metrics.producer_cache_refreshes, .Add, 1, .monotonic);
+                }
+                const space = CAPACITY -| @as(usize, @intCast(self.tail -% self.ring.cached_head));
+                if (space < self.n) return null;
+                return self.ring.makeReservation(self.tail, self.n);
+            }
+        };
+
+        /// Start a retryable reservation of n slots; see [ReserveAttempt].
+        pub fn reserveAttempt(self: *Self, n: usize) ReserveAttempt {
+            std.debug.assert(n != 0 and n <= CAPACITY);
+            return .{ .ring = self, .tail = self.tail.load(.monotonic), .n = n };
+        }
+
         /// Reserve with adaptive backoff. Spins, yields, then gives up.
         pub fn reserveWithBackoff(self: *Self, n: usize) ?Reservation(T) {
             var backoff = Backoff{};
+            return self.reserveWithBackoffUsing(n, &backoff);
+        }
+
+        /// `reserveWithBackoff` with a caller-supplied backoff, for users
+        /// who already standardized on their own policy type. The backoff
+        /// needs `snooze()` and `isCompleted()` — the duck-typed shape of
+        /// this file's [Backoff] — and arrives by pointer so its state
+        /// survives the call (inspect or `reset` it afterwards).
+        pub fn reserveWithBackoffUsing(self: *Self, n: usize, backoff: anytype) ?Reservation(T) {
+            if (n > CAPACITY) return null; // can never fit; don't burn the backoff
             while (!backoff.isCompleted()) {
                 if (self.reserve(n)) |r| return r;
                 if (self.isClosed()) return null;
@@ -191,21 +667,315 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
             return null;
         }
 
-        inline fn makeReservation(self: *Self, tail: u64, n: usize) Reservation(T) {
+        /// Reservation whose data pointer carries a comptime alignment.
+        pub fn AlignedReservation(comptime alignment: u29) type {
+            return struct {
+                /// Aligned region to write into (may be shorter than requested)
+                slice: []align(alignment) T,
+                /// Padding slots skipped before `slice`, already filled by
+                /// `reserveAligned`; commit `pad + written` afterwards
+                pad: usize,
+                pos: u64,
+            };
+        }
+
+        /// Reserve n slots whose base pointer is `alignment`-byte aligned,
+        /// for producers using aligned SIMD stores. Slots skipped to reach
+        /// the aligned start are filled with `pad_value` and become part of
+        /// the stream — the consumer will see them, so use a sentinel it
+        /// recognizes as padding. Commit `r.pad` plus the items written.
+        pub fn reserveAligned(self: *Self, n: usize, comptime alignment: u29, pad_value: T) ?AlignedReservation(alignment) {
+            comptime {
+                std.debug.assert(std.math.isPowerOfTwo(alignment));
+                // The slot arithmetic below can only deliver `alignment`
+                // relative to the buffer base, so the base must carry at
+                // least that much itself — with `pad_cache_lines` off the
+                // base collapses to @alignOf(T), and a 64-byte request
+                // against it would hand out a misaligned pointer.
+                if (alignment > BUF_ALIGN) {
+                    @compileError("reserveAligned alignment exceeds the buffer base alignment; keep pad_cache_lines on or lower the alignment");
+                }
+                if (@sizeOf(T) < alignment) {
+                    std.debug.assert(alignment % @sizeOf(T) == 0);
+                } else {
+                    std.debug.assert(@sizeOf(T) % alignment == 0);
+                }
+            }
+            const slots_per = if (@sizeOf(T) >= alignment) 1 else alignment / @sizeOf(T);
+
+            const tail = self.tail.load(.monotonic);
+            const idx: usize = @intCast(tail & MASK);
+            const pad = (slots_per - (idx % slots_per)) % slots_per;
+            if (pad + n > CAPACITY) return null;
+
+            const r = self.reserve(pad + n) orelse return null;
+            if (r.slice.len <= pad) return null; // aligned start past the wrap
+
+            @memset(r.slice[0..pad], pad_value);
+            return .{ .slice = @alignCast(r.slice[pad..]), .pad = pad, .pos = r.pos +% pad };
+        }
+
+        /// Zero-copy ingest for byte rings (`Ring(u8, ...)`): reserve the
+        /// contiguous free region, read directly into it from `reader`
+        /// (e.g. a socket — no bounce buffer), and commit what was read.
+        /// Does up to two reads per call to cover the wraparound. Returns
+        /// the total bytes ingested; 0 means the ring is full or EOF.
+        pub fn produceFromReader(self: *Self, reader: anytype) !usize {
+            comptime std.debug.assert(T == u8);
+            // An outstanding tracked grant would overlap the region this
+            // function reserves for itself — and be clobbered below.
+            std.debug.assert(self.reserved == 0);
+
+            var total: usize = 0;
+            var pass: usize = 0;
+            while (pass < 2) : (pass += 1) {
+                const tail = self.tail.load(.monotonic);
+                self.cached_head = self.head.load(.acquire);
+                const space = CAPACITY -| (tail -% self.cached_head);
+                if (space == 0) break;
+
+                const idx: usize = @intCast(tail & MASK);
+                const contiguous = @min(space, CAPACITY - idx);
+                const n = try reader.read(self.buffer[idx..][0..contiguous]);
+                if (n == 0) break; // EOF
+                self.reserved = contiguous; // record the grant for commit's check
+                self.commit(n);
+                total += n;
+                if (n < contiguous) break; // short read; don't block on more
+            }
+            // A short commit leaves a remainder grant; clear it so a later
+            // commitAll can't publish slots that were never written.
+            self.reserved = 0;
+            return total;
+        }
+
+        /// Like `reserve`, but remembers the reservation's length so
+        /// `commitAll` can commit exactly that amount. For producers that
+        /// always fill their whole reservation; guards against committing
+        /// the wrong count. One outstanding tracked reservation at a time.
+        pub inline fn reserveTracked(self: *Self, n: usize) ?Reservation(T) {
+            return self.reserve(n); // every reserve records its grant now
+        }
+
+        /// Commit the full length of the last `reserveTracked` reservation.
+        pub inline fn commitAll(self: *Self) void {
+            self.commit(self.reserved);
+            self.reserved = 0;
+        }
+
+        inline fn makeReservation(self: *Self, tail: Cursor, n: usize) Reservation(T) {
             const idx = tail & MASK;
             const contiguous = @min(n, CAPACITY - idx);
+            self.reserved = contiguous;
 
-            // Prefetch next batch location (hide memory latency)
-            const next_idx = (tail + n) & MASK;
+            // Prefetch next batch location (hide memory latency). The index
+            // is masked before the pointer is formed, so the prefetch
+            // address is always inside the buffer — never a past-the-end
+            // pointer that only the CPU happens to tolerate.
+            const next_idx = (tail +% @as(Cursor, @intCast(n))) & MASK;
             @prefetch(&self.buffer[next_idx], .{ .rw = .write, .locality = 3, .cache = .data });
 
-            return .{ .slice = self.buffer[idx..][0..contiguous], .pos = tail };
+            return .{ .slice = self.buffer[idx..][0..contiguous], .pos = tail, .requested = n };
         }
 
         /// Commit n slots after writing
         pub inline fn commit(self: *Self, n: usize) void {
+            self.commitWith(n, .release);
+        }
+
+        /// Commit with a caller-chosen store ordering.
+        ///
+        /// WARNING: `commit` uses `.release` because the SPSC guarantee
+        /// depends on it. A weaker ordering here is only sound if the caller
+        /// provides an equivalent fence externally (e.g. batching several
+        /// commits under a single fence). Misuse breaks the channel's
+        /// visibility and FIFO guarantees.
+        pub inline fn commitWith(self: *Self, n: usize, comptime order: std.builtin.AtomicOrder) void {
+            // A zero commit (reserve-then-abort) must not re-store tail:
+            // the unchanged value would still invalidate the consumer's line
+            if (n == 0) return;
+
+            const tail = self.tail.load(.monotonic);
+            // Committing more than was reservable advances tail past valid
+            // data and hands the consumer garbage; catch it in safe builds.
+            std.debug.assert(n <= CAPACITY - (tail -% self.head.load(.monotonic)));
+            // The classic partial-reservation footgun: reserve(n) clipped
+            // at the wrap, the caller commits n anyway. Check against the
+            // outstanding granted length too.
+            std.debug.assert(n <= self.reserved);
+            self.reserved -|= n;
+
+            if (CANARY_ENABLED) {
+                var i: usize = 0;
+                while (i < n) : (i += 1) {
+                    self.canaries[(tail +% @as(Cursor, @intCast(i))) & MASK] = CANARY;
+                }
+            }
+
+            if (config.track_dwell) {
+                const now = std.time.Instant.now() catch unreachable;
+                var i: usize = 0;
+                while (i < n) : (i += 1) {
+                    self.timestamps[(tail +% @as(Cursor, @intCast(i))) & MASK] = now;
+                }
+            }
+
+            self.tail.store(tail +% @as(Cursor, @intCast(n)), order);
+
+            if (config.enable_metrics) {
+                _ = @atomicRmw(u64, &self.metrics.messages_sent, .Add, n, .monotonic);
+                _ = @atomicRmw(u64, &self.metrics.batches_sent, .Add, 1, .monotonic);
+            }
+        }
+
+        /// Commit batcher trading freshness for cheaper publication: the
+        /// committed count accumulates in the producer-local `pending`
+        /// and the shared tail is stored — once, with `.release` — only
+        /// when `threshold` items have piled up or the caller flushes
+        /// explicitly (which it must before blocking or handing off).
+        /// The consumer's line is untouched between flushes, and every
+        /// publication carries the release edge the SPSC protocol needs;
+        /// the cost is that pending items stay invisible to the consumer
+        /// until the next flush.
+        ///
+        /// Reserve through the committer (`reserve`/`commit` here, not on
+        /// the ring) while batching: the ring's own tail lags by
+        /// `pending`, so mixing in `ring.reserve` or `ring.commit` while
+        /// `pending != 0` would hand out slots twice. Measure the
+        /// trade-off with the benchmark driver before tuning the
+        /// threshold up.
+        pub const AutoCommitter = struct {
+            ring: *Self,
+            threshold: usize,
+            pending: usize = 0,
+
+            /// `Ring.reserve` against the producer-local tail
+            /// (shared tail plus `pending`), so unpublished items still
+            /// occupy their slots.
+            pub fn reserve(self: *AutoCommitter, n: usize) ?Reservation(T) {
+                std.debug.assert(n <= CAPACITY);
+                if (n == 0 or n > CAPACITY) return null;
+
+                const ring = self.ring;
+                const tail = ring.tail.load(.monotonic) +% @as(Cursor, @intCast(self.pending));
+
+                var space = CAPACITY -| (tail -% ring.cached_head);
+                if (space < n) {
+                    ring.cached_head = ring.head.load(.acquire);
+                    if (config.enable_metrics) {
+                        _ = @atomicRmw(u64, &ring.metrics.producer_cache_refreshes, .Add, 1, .monotonic);
+                    }
+                    space = CAPACITY -| (tail -% ring.cached_head);
+                    if (space < n) return null;
+                }
+
+                return ring.makeReservation(tail, n);
+            }
+
+            /// Stage n written slots for the next flush. Stamps canaries
+            /// and dwell timestamps like `Ring.commit`, but does not
+            /// touch the shared tail.
+            pub fn commit(self: *AutoCommitter, n: usize) void {
+                if (n == 0) return;
+
+                const ring = self.ring;
+                const tail = ring.tail.load(.monotonic) +% @as(Cursor, @intCast(self.pending));
+                std.debug.assert(n <= ring.reserved);
+                ring.reserved -|= n;
+
+                if (CANARY_ENABLED) {
+                    var i: usize = 0;
+                    while (i < n) : (i += 1) {
+                        ring.canaries[(tail +% @as(Cursor, @intCast(i))) & MASK] = CANARY;
+                    }
+                }
+
+                if (config.track_dwell) {
+                    const now = std.time.Instant.now() catch unreachable;
+                    var i: usize = 0;
+                    while (i < n) : (i += 1) {
+                        ring.timestamps[(tail +% @as(Cursor, @intCast(i))) & MASK] = now;
+                    }
+                }
+
+                self.pending += n;
+                if (self.pending >= self.threshold) self.flush();
+            }
+
+            /// Publish everything staged so far: one `.release` tail
+            /// store covering the whole batch.
+            pub fn flush(self: *AutoCommitter) void {
+                if (self.pending == 0) return;
+
+                const ring = self.ring;
+                const tail = ring.tail.load(.monotonic);
+                ring.tail.store(tail +% @as(Cursor, @intCast(self.pending)), .release);
+
+                if (config.enable_metrics) {
+                    _ = @atomicRmw(u64, &ring.metrics.messages_sent, .Add, self.pending, .monotonic);
+                    _ = @atomicRmw(u64, &ring.metrics.batches_sent, .Add, 1, .monotonic);
+                }
+
+                self.pending = 0;
+            }
+
+            /// Retiring hand-off: flush, *then* set the closed flag. The
+            /// order matters — a bare `ring.close()` under this committer
+            /// strands the final sub-threshold batch, since the shared
+            /// tail hasn't moved for it yet and the consumer's close
+            /// drain would stop short of the staged items.
+            pub fn close(self: *AutoCommitter) void {
+                self.flush();
+                self.ring.close();
+            }
+        };
+
+        pub fn autoCommitter(self: *Self, threshold: usize) AutoCommitter {
+            std.debug.assert(threshold >= 1);
+            return .{ .ring = self, .threshold = threshold };
+        }
+
+        /// Publish everything this producer has written so far with a full
+        /// barrier: a `.seq_cst` read-modify-write of tail, which both acts
+        /// as a full fence and re-publishes the current tail. Pairs with
+        /// out-of-band wakeups (eventfd, futex): after `flush` returns, a
+        /// consumer woken through any mechanism observes all prior writes,
+        /// including commits made with relaxed `commitWith` orderings. A
+        /// plain `commit` already releases tail, so `flush` is only needed
+        /// when batching under weaker orderings or signaling externally.
+        pub fn flush(self: *Self) void {
+            _ = self.tail.fetchAdd(0, .seq_cst);
+        }
+
+        /// Checked commit: loads the authoritative head (one extra
+        /// `.acquire`) and verifies n slots were actually reservable before
+        /// advancing tail. For callers that can't vouch for their own
+        /// bookkeeping; `commit` stays the trusting fast path.
+        pub fn tryCommit(self: *Self, n: usize) error{Overflow}!void {
             const tail = self.tail.load(.monotonic);
-            self.tail.store(tail +% n, .release);
+            const head = self.head.load(.acquire);
+            if (n > CAPACITY - (tail -% head)) return error.Overflow;
+            // Same grant accounting as `commit`: publishing more than the
+            // outstanding reservation is the very bug this checks for.
+            if (n > self.reserved) return error.Overflow;
+            self.reserved -|= n;
+
+            if (CANARY_ENABLED) {
+                var i: usize = 0;
+                while (i < n) : (i += 1) {
+                    self.canaries[(tail +% @as(Cursor, @intCast(i))) & MASK] = CANARY;
+                }
+            }
+
+            if (config.track_dwell) {
+                const now = std.time.Instant.now() catch unreachable;
+                var i: usize = 0;
+                while (i < n) : (i += 1) {
+                    self.timestamps[(tail +% @as(Cursor, @intCast(i))) & MASK] = now;
+                }
+            }
+
+            self.tail.store(tail +% @as(Cursor, @intCast(n)), .release);
 
             if (config.enable_metrics) {
                 _ = @atomicRmw(u64, &self.metrics.messages_sent, .Add, n, .monotonic);
@@ -226,6 +996,9 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
             if (avail == 0) {
                 // Slow path: refresh cache
                 self.cached_tail = self.tail.load(.acquire);
+                if (config.enable_metrics) {
+                    _ = @atomicRmw(u64, &self.metrics.consumer_cache_refreshes, .Add, 1, .monotonic);
+                }
                 avail = self.cached_tail -% head;
                 if (avail == 0) return null;
             }
@@ -233,22 +1006,203 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
             const idx = head & MASK;
             const contiguous = @min(avail, CAPACITY - idx);
 
-            // Prefetch ahead for next read
-            const next_idx = (head + contiguous) & MASK;
+            // Prefetch ahead for next read; masked first, so the address is
+            // always in-bounds (same contract as the producer-side prefetch)
+            const next_idx = (head +% @as(Cursor, @intCast(contiguous))) & MASK;
             @prefetch(&self.buffer[next_idx], .{ .rw = .read, .locality = 3, .cache = .data });
 
             return self.buffer[idx..][0..contiguous];
         }
 
+        /// Buffer index of the consumer cursor (`head & mask`), for
+        /// hand-tuned loops that index the buffer directly.
+        pub inline fn readIndex(self: *const Self) usize {
+            return @intCast(self.head.load(.monotonic) & MASK);
+        }
+
+        /// Contiguous elements from the read index to the wrap boundary.
+        /// A vectorized consumer can split its SIMD loop here without
+        /// peeking and inspecting slice lengths: it gets
+        /// `min(len(), distanceToWrap())` elements before the wrap.
+        pub inline fn distanceToWrap(self: *const Self) usize {
+            return CAPACITY - self.readIndex();
+        }
+
+        /// Like `readable`, but returns an empty slice instead of null so
+        /// consumers can use normal slice iteration unconditionally.
+        /// The data stays valid until the next `advance` (single consumer).
+        pub inline fn peekSlice(self: *Self) []const T {
+            return self.readable() orelse &[_]T{};
+        }
+
+        /// `peekSlice` with a bounded spin: re-checks tail up to
+        /// `max_spins` times before conceding empty. A middle ground
+        /// between the immediate peek and a parking wait, for consumers
+        /// where data is almost always a few hundred nanoseconds away —
+        /// the spin amortizes the call overhead of polling.
+        pub fn peekSpin(self: *Self, max_spins: usize) []const T {
+            var spins: usize = 0;
+            while (true) {
+                const slice = self.peekSlice();
+                if (slice.len != 0 or spins >= max_spins) return slice;
+                spins += 1;
+                std.atomic.spinLoopHint();
+            }
+        }
+
+        /// One-shot answer to "spin or exit?" — see `peekStatus`.
+        pub const PeekStatus = union(enum) {
+            /// Readable run up to the wrap; same lifetime as `peekSlice`.
+            data: []const T,
+            /// Nothing readable, ring still open: keep polling.
+            empty,
+            /// Nothing readable and the close was observed before the
+            /// emptiness check, so no straggler commit can be in flight:
+            /// safe to exit.
+            closed_empty,
+        };
+
+        /// `peekSlice` and the shutdown check folded into one call, so the
+        /// consumer's drain loop cannot race a separate `isClosed` against
+        /// `isEmpty`: the closed flag is loaded *before* the data check,
+        /// and `close` is a `.release` store after the producer's last
+        /// commit — if `.closed_empty` comes back, every commit that
+        /// preceded the close has already been seen by this or an earlier
+        /// peek. Checking the flags the other way around (empty, then
+        /// closed) would let a commit+close land between the two reads and
+        /// strand the tail of the stream.
+        pub fn peekStatus(self: *Self) PeekStatus {
+            const was_closed = self.closed.load(.acquire);
+            const slice = self.peekSlice();
+            if (slice.len != 0) return .{ .data = slice };
+            return if (was_closed) .closed_empty else .empty;
+        }
+
+        /// The two readable runs around the wrap boundary.
+        pub const Segments = struct {
+            /// Pre-wrap run (empty when the ring is empty)
+            first: []const T,
+            /// Post-wrap run (empty when the data doesn't wrap)
+            second: []const T,
+        };
+
+        /// Both contiguous runs in one call, so a consumer near the wrap
+        /// boundary can process the entire backlog without a second
+        /// `readable` round trip. Advance `first.len + second.len` to
+        /// consume everything returned.
+        pub fn peekBoth(self: *Self) Segments {
+            const head = self.head.load(.monotonic);
+            self.cached_tail = self.tail.load(.acquire);
+
+            const avail = self.cached_tail -% head;
+            if (avail == 0) return .{ .first = &[_]T{}, .second = &[_]T{} };
+
+            const idx = head & MASK;
+            const contiguous = @min(avail, CAPACITY - idx);
+            const rest: usize = @intCast(avail - contiguous);
+
+            return .{
+                .first = self.buffer[idx..][0..contiguous],
+                .second = self.buffer[0..rest],
+            };
+        }
+
+        /// Copy up to `dst.len` readable items into `dst` WITHOUT
+        /// advancing — the non-consuming counterpart of `recv`, crossing
+        /// the wrap in one call. Snapshot the front of the queue into a
+        /// scratch buffer, inspect it, then `advance` by however many
+        /// were accepted. Returns the number copied.
+        pub fn peekInto(self: *Self, dst: []T) usize {
+            const segs = self.peekBoth();
+            const n = @min(segs.first.len + segs.second.len, dst.len);
+            const take_first = @min(segs.first.len, n);
+            @memcpy(dst[0..take_first], segs.first[0..take_first]);
+            @memcpy(dst[take_first..n], segs.second[0 .. n - take_first]);
+            return n;
+        }
+
         /// Advance head after reading n items
         pub inline fn advance(self: *Self, n: usize) void {
+            self.advanceWith(n, .release);
+        }
+
+        /// Advance with a caller-chosen store ordering.
+        ///
+        /// WARNING: same contract as `commitWith` — only use a weaker
+        /// ordering when an external fence provides the `.release` edge,
+        /// otherwise slot reuse races with the producer's writes.
+        pub inline fn advanceWith(self: *Self, n: usize, comptime order: std.builtin.AtomicOrder) void {
+            // Mirror of commit: advancing by zero skips the store entirely
+            if (n == 0) return;
+
             const head = self.head.load(.monotonic);
-            self.head.store(head +% n, .release);
+            self.head.store(head +% @as(Cursor, @intCast(n)), order);
 
             if (config.enable_metrics) {
                 _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, n, .monotonic);
                 _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
+            }
+        }
+
+        /// Nanoseconds since the consumer last advanced head, for a
+        /// watchdog that wants to tell a stalled consumer apart from a
+        /// quiet one: a ring that is full *and* has a large value here has
+        /// producers spinning on a consumer that stopped draining — alarm
+        /// or fail fast instead of spinning silently. Requires
+        /// `enable_metrics`. Returns 0 until the first advance (a consumer
+        /// that never started shows as a full ring with no stamp at all).
+        /// Wall-clock based, so treat it as watchdog-granularity, not a
+        /// latency measurement — that's what `track_dwell` is for.
+        pub fn timeSinceLastConsume(self: *const Self) u64 {
+            comptime {
+                if (!config.enable_metrics) @compileError("timeSinceLastConsume requires Config.enable_metrics");
+            }
+            const last = self.last_consume_ns.load(.monotonic);
+            if (last == 0) return 0;
+            return wallNanos() -| last;
+        }
+
+        inline fn wallNanos() u64 {
+            return @intCast(std.time.nanoTimestamp());
+        }
+
+        /// Set head to an absolute cursor position, for a consumer
+        /// replaying from a checkpoint (e.g. resuming from a saved cursor
+        /// in the shared-memory scenario). The position must lie in
+        /// `[old_head, tail]` — debug builds assert it; in release a bad
+        /// position silently re-reads or skips items and corrupts the
+        /// ring's accounting. Does not touch the batch metrics, since a
+        /// replay seek is not a consumption event.
+        pub fn advanceTo(self: *Self, position: Cursor) void {
+            const old = self.head.load(.monotonic);
+            std.debug.assert(position -% old <= self.tail.load(.acquire) -% old);
+            self.head.store(position, .release);
+        }
+
+        /// Non-consuming iterator over the `[head, tail)` window.
+        /// `tail` is snapshotted at creation, so items committed after the
+        /// iterator is created are not yielded.
+        pub const PeekIter = struct {
+            ring: *const Self,
+            pos: Cursor,
+            end: Cursor,
+
+            pub fn next(it: *PeekIter) ?*const T {
+                if (it.pos == it.end) return null;
+                const item = &it.ring.buffer[it.pos & MASK];
+                it.pos +%= 1;
+                return item;
             }
+        };
+
+        /// Iterate over available items without advancing head.
+        /// The read-only counterpart to `consumeBatch`: lets the consumer
+        /// scan and decide before committing to `advance`.
+        pub fn peekIter(self: *const Self) PeekIter {
+            const head = self.head.load(.monotonic);
+            const tail = self.tail.load(.acquire);
+            return .{ .ring = self, .pos = head, .end = tail };
         }
 
         // ---------------------------------------------------------------------
@@ -257,6 +1211,9 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
 
         /// Process ALL available items with a single head update.
         /// This is the Disruptor's secret sauce - amortizes atomic operations.
+        /// A huge burst holds the CPU for the whole drain; consumers that
+        /// multiplex with other event sources should use `consumeUpTo`,
+        /// which caps the batch and returns control to the loop.
         pub fn consumeBatch(self: *Self, handler: anytype) usize {
             const head = self.head.load(.monotonic);
             const tail = self.tail.load(.acquire);
@@ -267,9 +1224,15 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
             var pos = head;
             var count: usize = 0;
 
-            // Process all available items
+            // Process all available items, prefetching ahead so the loop
+            // isn't stalled on memory per element during large drains
             while (pos != tail) {
                 const idx = pos & MASK;
+                @prefetch(&self.buffer[(pos +% PREFETCH_DISTANCE) & MASK], .{ .rw = .read, .locality = 3, .cache = .data });
+                if (CANARY_ENABLED) {
+                    std.debug.assert(self.canaries[idx] == CANARY); // torn read: slot not committed
+                    self.canaries[idx] = 0;
+                }
                 handler.process(&self.buffer[idx]);
                 pos +%= 1;
                 count += 1;
@@ -281,252 +1244,1922 @@ pub fn Ring(comptime T: type, comptime config: Config) type {
             if (config.enable_metrics) {
                 _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, count, .monotonic);
                 _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
             }
 
             return count;
         }
 
-        /// Consume up to max_items items with a single head update.
-        /// Useful for real-world processing where large batches may block too long.
-        pub fn consumeUpTo(self: *Self, max_items: usize, handler: anytype) usize {
-            if (max_items == 0) return 0;
-
+        /// `consumeBatch` variant handing the handler a mutable pointer,
+        /// for process-and-clear consumers — zeroizing sensitive payloads
+        /// after reading, or transforming in place without moving the
+        /// value out. Sound because the producer never touches
+        /// `[head, tail)` until head advances. The handler needs
+        /// `pub fn process(self, item: *T)`.
+        pub fn consumeBatchMut(self: *Self, handler: anytype) usize {
             const head = self.head.load(.monotonic);
             const tail = self.tail.load(.acquire);
-
-            const avail = tail -% head;
-            if (avail == 0) return 0;
-
-            const to_consume = @min(avail, max_items);
+            if (tail -% head == 0) return 0;
 
             var pos = head;
             var count: usize = 0;
-
-            // Process up to max_items
-            while (count < to_consume) {
+            while (pos != tail) {
                 const idx = pos & MASK;
+                if (CANARY_ENABLED) {
+                    std.debug.assert(self.canaries[idx] == CANARY); // torn read: slot not committed
+                    self.canaries[idx] = 0;
+                }
                 handler.process(&self.buffer[idx]);
                 pos +%= 1;
                 count += 1;
             }
 
-            // Single atomic update for the batch
-            self.head.store(head +% count, .release);
+            self.head.store(tail, .release);
 
             if (config.enable_metrics) {
                 _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, count, .monotonic);
                 _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
             }
 
             return count;
         }
 
-        /// Consume batch with callback function
-        pub fn consumeBatchFn(self: *Self, comptime callback: fn (*const T) void) usize {
-            const Handler = struct {
-                pub fn process(item: *const T) void {
-                    callback(item);
-                }
-            };
-            return self.consumeBatch(Handler);
+        /// Result pair of `consumeFold`: the final accumulator plus the
+        /// items consumed (count 0 means `acc` is `init` untouched).
+        pub fn Folded(comptime A: type) type {
+            return struct { acc: A, count: usize };
         }
 
-        // ---------------------------------------------------------------------
-        // CONVENIENCE WRAPPERS
-        // ---------------------------------------------------------------------
+        /// `consumeBatch` for consumers that reduce the batch to a
+        /// summary — a sum, max, checksum — threading the accumulator
+        /// through the calls instead of mutating captured state inside a
+        /// handler. The folder needs
+        /// `pub fn fold(self, acc: A, item: *const T) A`.
+        /// Same single head update as `consumeBatch`.
+        pub fn consumeFold(self: *Self, comptime A: type, init: A, folder: anytype) Folded(A) {
+            const head = self.head.load(.monotonic);
+            const tail = self.tail.load(.acquire);
+            var acc = init;
+            if (tail -% head == 0) return .{ .acc = acc, .count = 0 };
 
-        /// Batch send (convenience)
-        pub inline fn send(self: *Self, items: []const T) usize {
-            const r = self.reserve(items.len) orelse return 0;
-            @memcpy(r.slice, items[0..r.slice.len]);
-            self.commit(r.slice.len);
-            return r.slice.len;
-        }
+            var pos = head;
+            var count: usize = 0;
+            while (pos != tail) {
+                const idx = pos & MASK;
+                @prefetch(&self.buffer[(pos +% PREFETCH_DISTANCE) & MASK], .{ .rw = .read, .locality = 3, .cache = .data });
+                if (CANARY_ENABLED) {
+                    std.debug.assert(self.canaries[idx] == CANARY); // torn read: slot not committed
+                    self.canaries[idx] = 0;
+                }
+                acc = folder.fold(acc, &self.buffer[idx]);
+                pos +%= 1;
+                count += 1;
+            }
 
-        /// Batch receive (convenience)
-        pub inline fn recv(self: *Self, out: []T) usize {
-            const slice = self.readable() orelse return 0;
-            const n = @min(slice.len, out.len);
-            @memcpy(out[0..n], slice[0..n]);
-            self.advance(n);
-            return n;
-        }
+            self.head.store(tail, .release);
 
-        // ---------------------------------------------------------------------
-        // LIFECYCLE
-        // ---------------------------------------------------------------------
+            if (config.enable_metrics) {
+                _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, count, .monotonic);
+                _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
+            }
 
-        pub fn close(self: *Self) void {
-            self.closed.store(true, .release);
+            return .{ .acc = acc, .count = count };
         }
 
-        pub fn getMetrics(self: *const Self) Metrics {
+        /// `consumeBatch` that also reports each item's time in the ring,
+        /// for latency-SLA monitoring that raw throughput can't provide.
+        /// Requires `track_dwell` in the config; the handler needs
+        /// `pub fn process(self, item: *const T, age_ns: u64)`. The age is
+        /// measured from the item's commit to this drain's entry.
+        pub fn consumeBatchTimed(self: *Self, handler: anytype) usize {
+            comptime {
+                if (!config.track_dwell) @compileError("consumeBatchTimed requires Config.track_dwell");
+            }
+
+            const head = self.head.load(.monotonic);
+            const tail = self.tail.load(.acquire);
+            if (tail -% head == 0) return 0;
+
+            const now = std.time.Instant.now() catch unreachable;
+            var pos = head;
+            var count: usize = 0;
+            while (pos != tail) {
+                const idx = pos & MASK;
+                const age = if (now.order(self.timestamps[idx]) == .gt) now.since(self.timestamps[idx]) else 0;
+                handler.process(&self.buffer[idx], age);
+                pos +%= 1;
+                count += 1;
+            }
+
+            self.head.store(tail, .release);
+
             if (config.enable_metrics) {
-                return self.metrics;
+                _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, count, .monotonic);
+                _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
             }
-            return .{};
+
+            return count;
         }
-    };
-}
 
-// ============================================================================
-// MPSC CHANNEL - Multiple Producers, Single Consumer
-// ============================================================================
+        /// `consumeBatch` that re-snapshots tail after each drain and keeps
+        /// going while the producer has advanced it, so a firehose producer
+        /// doesn't force a return to the caller between bursts. Bounded at
+        /// `max_rounds` snapshots — the live-lock cap; a producer that
+        /// outruns the consumer indefinitely still yields control after
+        /// that many batches. Returns total items consumed.
+        pub fn consumeBatchGreedy(self: *Self, handler: anytype, max_rounds: usize) usize {
+            var total: usize = 0;
+            var rounds: usize = 0;
+            while (rounds < max_rounds) : (rounds += 1) {
+                const n = self.consumeBatch(handler);
+                if (n == 0) break;
+                total += n;
+            }
+            return total;
+        }
 
-pub fn Channel(comptime T: type, comptime config: Config) type {
-    const RingType = Ring(T, config);
+        /// Drain loop with the close handshake built in. The protocol:
+        /// producers commit everything, then `close`; the consumer checks
+        /// `isClosed` only after an empty drain, and after observing the
+        /// flag drains once more before returning. The `.release` store in
+        /// `close` paired with the `.acquire` load here guarantees every
+        /// commit that happened before the close is visible to that final
+        /// drain, so the tail of the stream is never lost — even when the
+        /// producer closes while the ring is still full. Returns the total
+        /// items consumed.
+        pub fn consumeUntilClosed(self: *Self, handler: anytype) usize {
+            var total: usize = 0;
+            while (true) {
+                const n = self.consumeBatch(handler);
+                total += n;
+                if (n != 0) continue;
+
+                if (self.closed.load(.acquire)) {
+                    while (true) {
+                        const last = self.consumeBatch(handler);
+                        if (last == 0) return total;
+                        total += last;
+                    }
+                }
+                std.atomic.spinLoopHint();
+            }
+        }
 
-    return struct {
-        const Self = @This();
+        /// A single word that changes whenever this ring has something new
+        /// to observe: committed data (the tail moved) or a close. A
+        /// consumer multiplexing several rings remembers the last stamp
+        /// per ring and compares against `eventStamp` in its select loop —
+        /// one pass over the stamps detects data *and* shutdown, so a
+        /// multi-channel event loop needs no separate `isClosed` poll to
+        /// tear down cleanly. The close bit rides in bit 0 (the tail is
+        /// shifted up), so a close changes the stamp even when no data
+        /// ever arrives.
+        pub fn eventStamp(self: *const Self) u64 {
+            // Load order matters: reading `closed` first means a stamp
+            // with the bit clear can never have swallowed a close that
+            // preceded the observed tail.
+            const c: u64 = @intFromBool(self.closed.load(.acquire));
+            const t: u64 = self.tail.load(.acquire);
+            return (t << 1) | c;
+        }
 
-        rings: [config.max_producers]RingType = [_]RingType{.{}} ** config.max_producers,
-        producer_count: std.atomic.Value(usize) = std.atomic.Value(usize).init(0),
-        closed: std.atomic.Value(bool) = std.atomic.Value(bool).init(false),
+        /// Spin until the stamp differs from `last`; returns the new
+        /// stamp. Pair with `eventStamp`: take a stamp, drain, and wait on
+        /// the stamp you took — data committed in between changes the word
+        /// and the wait returns immediately, so the handshake cannot lose
+        /// a wakeup. Returns on new data or on close alike; the caller
+        /// checks `isClosed` (after a final drain) to tell them apart.
+        pub fn waitEvent(self: *const Self, last: u64) u64 {
+            var backoff = Backoff{};
+            while (true) {
+                const stamp = self.eventStamp();
+                if (stamp != last) return stamp;
+                backoff.snooze();
+            }
+        }
 
-        pub const Producer = struct {
-            ring: *RingType,
-            id: usize,
+        /// Consume up to max_items items with a single head update.
+        /// Useful for real-world processing where large batches may block too long.
+        pub fn consumeUpTo(self: *Self, max_items: usize, handler: anytype) usize {
+            if (max_items == 0) return 0;
 
-            pub inline fn reserve(self: Producer, n: usize) ?Reservation(T) {
-                return self.ring.reserve(n);
+            const head = self.head.load(.monotonic);
+            const tail = self.tail.load(.acquire);
+
+            const avail = tail -% head;
+            if (avail == 0) return 0;
+
+            const to_consume = @min(avail, max_items);
+
+            var pos = head;
+            var count: usize = 0;
+
+            // Process up to max_items
+            while (count < to_consume) {
+                const idx = pos & MASK;
+                handler.process(&self.buffer[idx]);
+                pos +%= 1;
+                count += 1;
             }
 
-            pub inline fn reserveWithBackoff(self: Producer, n: usize) ?Reservation(T) {
-                return self.ring.reserveWithBackoff(n);
+            // Single atomic update for the batch
+            self.head.store(head +% @as(Cursor, @intCast(count)), .release);
+
+            if (config.enable_metrics) {
+                _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, count, .monotonic);
+                _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
             }
 
-            pub inline fn commit(self: Producer, n: usize) void {
-                self.ring.commit(n);
+            return count;
+        }
+
+        /// Issue prefetches for every cache line spanning `count` items
+        /// starting at the free-running cursor `pos`, wraparound included.
+        /// One call warms a whole known-size batch upfront, where the
+        /// rolling `PREFETCH_DISTANCE` in `consumeBatch` only stays one
+        /// stride ahead. Indices are masked before any pointer is formed
+        /// (same in-bounds contract as the other prefetch sites).
+        pub fn prefetchRange(self: *const Self, pos: Cursor, count: usize) void {
+            const line_items = @max(64 / @sizeOf(T), 1);
+            var i: usize = 0;
+            while (i < count) : (i += line_items) {
+                const idx = (pos +% @as(Cursor, @intCast(i))) & MASK;
+                @prefetch(&self.buffer[idx], .{ .rw = .read, .locality = 3, .cache = .data });
             }
+        }
 
-            pub inline fn send(self: Producer, items: []const T) usize {
-                return self.ring.send(items);
+        /// All-or-nothing consume for fixed-size frames: returns false
+        /// without advancing when fewer than n items are available,
+        /// otherwise runs the handler over exactly n and advances once.
+        /// Saves frame-oriented consumers from buffering partial frames.
+        pub fn consumeExact(self: *Self, n: usize, handler: anytype) bool {
+            if (n == 0) return true;
+
+            const head = self.head.load(.monotonic);
+            const tail = self.tail.load(.acquire);
+
+            if (tail -% head < n) return false;
+
+            // The batch size is known upfront — warm the whole region now
+            self.prefetchRange(head, n);
+
+            var pos = head;
+            var count: usize = 0;
+            while (count < n) : ({
+                pos +%= 1;
+                count += 1;
+            }) {
+                handler.process(&self.buffer[pos & MASK]);
             }
-        };
 
-        pub fn init() Self {
-            return .{};
+            self.head.store(head +% @as(Cursor, @intCast(n)), .release);
+
+            if (config.enable_metrics) {
+                _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, n, .monotonic);
+                _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                self.last_consume_ns.store(wallNanos(), .monotonic);
+            }
+
+            return true;
         }
 
-        pub fn register(self: *Self) error{ TooManyProducers, Closed }!Producer {
-            if (self.closed.load(.acquire)) return error.Closed;
+        /// Work-stealing drain: several consumer threads share one ring,
+        /// each claiming a disjoint batch of up to `max_items` via CAS on
+        /// `shared_claim`. Parallelizes an otherwise single-consumer
+        /// firehose at the cost of strict ordering across consumers (each
+        /// batch is still FIFO internally).
+        ///
+        /// The claim cursor only partitions work; `head` is the completion
+        /// cursor and advances after a batch has been fully processed, so
+        /// the producer never overwrites slots a consumer is still reading.
+        /// Completions publish in claim order: a consumer that finishes out
+        /// of turn spins until the batches claimed before it have retired.
+        ///
+        /// CAVEATS: do not mix with the single-consumer API on the same
+        /// ring while shared consumers are active — they race the claim
+        /// cursor against plain head stores.
+        pub fn consumeShared(self: *Self, max_items: usize, handler: anytype) usize {
+            if (max_items == 0) return 0;
 
-            const id = self.producer_count.fetchAdd(1, .monotonic);
-            if (id >= config.max_producers) {
-                _ = self.producer_count.fetchSub(1, .monotonic);
-                return error.TooManyProducers;
+            while (true) {
+                const claim = self.shared_claim.load(.monotonic);
+                const tail = self.tail.load(.acquire);
+
+                const avail = tail -% claim;
+                if (avail == 0) return 0;
+
+                const n = @min(avail, max_items);
+
+                // Claim [claim, claim + n); on contention another consumer
+                // won the range, so re-read and try the next one
+                if (self.shared_claim.cmpxchgWeak(claim, claim +% @as(Cursor, @intCast(n)), .acq_rel, .monotonic) != null) continue;
+
+                var pos = claim;
+                var count: usize = 0;
+                while (count < n) : ({
+                    pos +%= 1;
+                    count += 1;
+                }) {
+                    handler.process(&self.buffer[pos & MASK]);
+                }
+
+                // Retire in claim order: wait for earlier batches to finish,
+                // then free our range to the producer in one release store.
+                while (self.head.load(.monotonic) != claim) {
+                    std.atomic.spinLoopHint();
+                }
+                self.head.store(claim +% @as(Cursor, @intCast(n)), .release);
+
+                if (config.enable_metrics) {
+                    _ = @atomicRmw(u64, &self.metrics.messages_received, .Add, n, .monotonic);
+                    _ = @atomicRmw(u64, &self.metrics.batches_received, .Add, 1, .monotonic);
+                    self.last_consume_ns.store(wallNanos(), .monotonic);
+                }
+
+                return n;
             }
+        }
 
-            self.rings[id].active.store(true, .release);
-            return .{ .ring = &self.rings[id], .id = id };
+        /// Consume batch with callback function
+        pub fn consumeBatchFn(self: *Self, comptime callback: fn (*const T) void) usize {
+            const Handler = struct {
+                pub fn process(item: *const T) void {
+                    callback(item);
+                }
+            };
+            return self.consumeBatch(Handler);
         }
 
-        /// Round-robin receive from all active producers
-        pub fn recv(self: *Self, out: []T) usize {
-            var total: usize = 0;
-            const count = self.producer_count.load(.acquire);
-            for (self.rings[0..count]) |*ring| {
-                if (total >= out.len) break;
-                total += ring.recv(out[total..]);
-            }
-            return total;
+        // ---------------------------------------------------------------------
+        // CONVENIENCE WRAPPERS
+        // ---------------------------------------------------------------------
+
+        /// Batch send (convenience)
+        pub inline fn send(self: *Self, items: []const T) usize {
+            const r = self.reserve(items.len) orelse return 0;
+            @memcpy(r.slice, items[0..r.slice.len]);
+            self.commit(r.slice.len);
+            return r.slice.len;
         }
 
-        /// Batch consume from all producers - THE FAST PATH
-        pub fn consumeAll(self: *Self, handler: anytype) usize {
-            var total: usize = 0;
-            const count = self.producer_count.load(.acquire);
-            for (0..count) |i| {
-                total += self.rings[i].consumeBatch(handler);
+        /// Send a single value with an explicit full-ring policy, so call
+        /// sites don't each reimplement drop/block/fail around `reserve`.
+        pub fn sendWith(self: *Self, value: T, on_full: OnFull) SendOutcome {
+            if (self.trySendOne(value)) return .sent;
+            switch (on_full) {
+                .drop_new => {
+                    if (config.enable_metrics) {
+                        _ = @atomicRmw(u64, &self.metrics.messages_dropped, .Add, 1, .monotonic);
+                    }
+                    return .dropped;
+                },
+                .block => {
+                    while (!self.isClosed()) {
+                        if (self.reserveWithBackoff(1)) |r| {
+                            r.slice[0] = value;
+                            self.commit(1);
+                            return .sent;
+                        }
+                    }
+                    return .full;
+                },
+                .fail => return .full,
             }
-            return total;
         }
 
-        /// Consume up to max_total items from all producers, preferring earlier rings.
-        /// Useful for real-world processing to limit batch size and avoid long pauses.
-        pub fn consumeAllUpTo(self: *Self, max_total: usize, handler: anytype) usize {
-            var total: usize = 0;
-            const count = self.producer_count.load(.acquire);
-            for (0..count) |i| {
-                if (total >= max_total) break;
-                const remaining = max_total - total;
-                total += self.rings[i].consumeUpTo(remaining, handler);
+        /// Always-succeeding push for "latest N samples" buffers: when
+        /// full, the oldest item is dropped (head advanced by one) to make
+        /// room, so the ring always holds the most recent `capacity()` values.
+        /// Moving the consumer cursor from the producer side breaks the
+        /// SPSC split — only sound single-threaded or under external
+        /// exclusion, the usual shape for a diagnostics ring sampled in
+        /// place. Overwrites count as `messages_dropped` in the metrics.
+        pub fn pushOverwrite(self: *Self, value: T) void {
+            const tail = self.tail.load(.monotonic);
+            const head = self.head.load(.monotonic);
+
+            if (CAPACITY - @as(usize, @intCast(tail -% head)) == 0) {
+                self.head.store(head +% 1, .release);
+                if (config.enable_metrics) {
+                    _ = @atomicRmw(u64, &self.metrics.messages_dropped, .Add, 1, .monotonic);
+                }
             }
-            return total;
-        }
 
-        pub fn close(self: *Self) void {
-            self.closed.store(true, .release);
-            const count = self.producer_count.load(.acquire);
-            for (self.rings[0..count]) |*ring| {
-                ring.close();
+            self.buffer[tail & MASK] = value;
+            if (CANARY_ENABLED) {
+                self.canaries[tail & MASK] = CANARY;
+            }
+            if (config.track_dwell) {
+                self.timestamps[tail & MASK] = std.time.Instant.now() catch unreachable;
+            }
+            self.tail.store(tail +% 1, .release);
+
+            if (config.enable_metrics) {
+                _ = @atomicRmw(u64, &self.metrics.messages_sent, .Add, 1, .monotonic);
+                _ = @atomicRmw(u64, &self.metrics.batches_sent, .Add, 1, .monotonic);
             }
         }
 
-        pub fn isClosed(self: *const Self) bool {
-            return self.closed.load(.acquire);
+        inline fn trySendOne(self: *Self, value: T) bool {
+            const r = self.reserve(1) orelse return false;
+            r.slice[0] = value;
+            self.commit(1);
+            return true;
         }
 
-        pub fn producerCount(self: *const Self) usize {
-            return self.producer_count.load(.acquire);
+        /// One-shot enqueue: reserve(1), write, commit(1) folded into a
+        /// single call, so the most common producer operation needs no
+        /// reservation handling at the call site. False when full (the
+        /// value stays with the caller). `sendWith` layers a full-ring
+        /// policy on top of this; `pop` is the consumer counterpart.
+        pub inline fn push(self: *Self, value: T) bool {
+            return self.trySendOne(value);
         }
 
-        pub fn getMetrics(self: *const Self) Metrics {
-            var m = Metrics{};
-            const count = self.producer_count.load(.acquire);
-            for (self.rings[0..count]) |*ring| {
-                const rm = ring.getMetrics();
-                m.messages_sent += rm.messages_sent;
-                m.messages_received += rm.messages_received;
-                m.batches_sent += rm.batches_sent;
-                m.batches_received += rm.batches_received;
-            }
-            return m;
+        /// One-shot dequeue counterpart of `push`: copies out the oldest
+        /// item and advances past it, or null when empty.
+        pub inline fn pop(self: *Self) ?T {
+            const slice = self.readable() orelse return null;
+            const value = slice[0];
+            self.advance(1);
+            return value;
         }
-    };
-}
 
-// Type aliases
-pub const DefaultRing = Ring(u64, default_config);
-pub const DefaultChannel = Channel(u64, default_config);
+        /// Batch receive (convenience)
+        pub inline fn recv(self: *Self, out: []T) usize {
+            const slice = self.readable() orelse return 0;
+            const n = @min(slice.len, out.len);
+            @memcpy(out[0..n], slice[0..n]);
+            self.advance(n);
+            return n;
+        }
 
-// ============================================================================
-// TESTS
-// ============================================================================
+        /// Move up to `max` items into a caller-owned list, reusing its
+        /// capacity across calls. Unlike `recv` this crosses the wrap in
+        /// one call, and the only allocation is the list growing the first
+        /// few batches. Ownership of the moved items passes to `out`.
+        pub fn drainInto(self: *Self, out: *std.ArrayList(T), max: usize) !usize {
+            const segs = self.peekBoth();
+            const n = @min(segs.first.len + segs.second.len, max);
+            if (n == 0) return 0;
+
+            try out.ensureUnusedCapacity(n);
+            const take_first = @min(segs.first.len, n);
+            out.appendSliceAssumeCapacity(segs.first[0..take_first]);
+            out.appendSliceAssumeCapacity(segs.second[0 .. n - take_first]);
+            self.advance(n);
+            return n;
+        }
 
-test "ring: basic reserve/commit/readable/advance" {
-    var ring = Ring(u64, default_config){};
+        /// Allocate a FIFO copy of the readable `[head, tail)` run without
+        /// consuming it — the checkpoint half of snapshot persistence. The
+        /// wrap split is flattened, so `out[0]` is the oldest item. Caller
+        /// owns the returned slice; pair with `preloaded` to restore.
+        pub fn toOwnedItems(self: *Self, allocator: std.mem.Allocator) ![]T {
+            const segs = self.peekBoth();
+            const out = try allocator.alloc(T, segs.first.len + segs.second.len);
+            @memcpy(out[0..segs.first.len], segs.first);
+            @memcpy(out[segs.first.len..], segs.second);
+            return out;
+        }
 
-    // Write
-    const w = ring.reserve(4).?;
-    w.slice[0] = 100;
-    w.slice[1] = 200;
-    w.slice[2] = 300;
-    w.slice[3] = 400;
-    ring.commit(4);
+        // ---------------------------------------------------------------------
+        // SPLIT ENDS - one-producer/one-consumer discipline in the types
+        // ---------------------------------------------------------------------
 
-    try std.testing.expectEqual(@as(usize, 4), ring.len());
+        /// Write half from `split`: only the producer API is reachable, so
+        /// code holding this type cannot touch the consumer cursor.
+        pub const ProducerEnd = struct {
+            ring: *Self,
 
-    // Read
-    const r = ring.readable().?;
-    try std.testing.expectEqual(@as(u64, 100), r[0]);
-    try std.testing.expectEqual(@as(u64, 400), r[3]);
-    ring.advance(4);
+            pub inline fn reserve(self: ProducerEnd, n: usize) ?Reservation(T) {
+                return self.ring.reserve(n);
+            }
+
+            pub inline fn commit(self: ProducerEnd, n: usize) void {
+                self.ring.commit(n);
+            }
+
+            pub inline fn send(self: ProducerEnd, items: []const T) usize {
+                return self.ring.send(items);
+            }
+
+            pub inline fn flush(self: ProducerEnd) void {
+                self.ring.flush();
+            }
+
+            /// Producer retiring for good; the matching consumer end
+            /// observes it via `isClosed` and can drain-and-exit.
+            pub inline fn close(self: ProducerEnd) void {
+                self.ring.close();
+            }
+        };
+
+        /// Read half from `split`: only the consumer API is reachable.
+        pub const ConsumerEnd = struct {
+            ring: *Self,
+
+            pub inline fn peekSlice(self: ConsumerEnd) []const T {
+                return self.ring.peekSlice();
+            }
+
+            pub inline fn advance(self: ConsumerEnd, n: usize) void {
+                self.ring.advance(n);
+            }
+
+            pub inline fn consumeBatch(self: ConsumerEnd, handler: anytype) usize {
+                return self.ring.consumeBatch(handler);
+            }
+
+            pub inline fn recv(self: ConsumerEnd, out: []T) usize {
+                return self.ring.recv(out);
+            }
+
+            pub inline fn isClosed(self: ConsumerEnd) bool {
+                return self.ring.isClosed();
+            }
+        };
+
+        pub const Ends = struct {
+            producer: ProducerEnd,
+            consumer: ConsumerEnd,
+        };
+
+        /// Split the ring into its two ends, each exposing only one side
+        /// of the SPSC contract. Hand one end to each thread and the
+        /// surface alone rules out a consumer calling `reserve` or a
+        /// producer calling `advance`. Zig has no affine types, so not
+        /// duplicating an end stays the caller's responsibility — the
+        /// split narrows the API, it doesn't count owners.
+        pub fn split(self: *Self) Ends {
+            return .{
+                .producer = .{ .ring = self },
+                .consumer = .{ .ring = self },
+            };
+        }
+
+        // ---------------------------------------------------------------------
+        // LIFECYCLE
+        // ---------------------------------------------------------------------
+
+        /// Allocate a ring with a caller-supplied allocator (arena, slab,
+        /// pre-allocated pool, ...). The ring is O(capacity × @sizeOf(T))
+        /// and easily blows the stack at larger ring_bits; nothing else in
+        /// the channel ever allocates. The `.{}` initializer writes through
+        /// the heap pointer (result location), so no stack-sized temporary
+        /// is built and moved — safe to call for rings far larger than the
+        /// stack. Pair with `destroy`.
+        pub fn create(allocator: std.mem.Allocator) !*Self {
+            const self = try allocator.create(Self);
+            self.* = .{};
+            return self;
+        }
+
+        pub fn destroy(self: *Self, allocator: std.mem.Allocator) void {
+            allocator.destroy(self);
+        }
+
+        /// The restore half of snapshot persistence: a fresh ring already
+        /// holding `items` in FIFO order, tail set past them. Goes through
+        /// the normal send path, so debug canaries and dwell stamps are
+        /// laid down like any other commit. Asserts the checkpoint fits —
+        /// restore into a ring at least as large as the one saved.
+        pub fn preloaded(items: []const T) Self {
+            var self = Self{};
+            const sent = self.send(items);
+            std.debug.assert(sent == items.len);
+            return self;
+        }
+
+        /// Mark the ring closed. A `.release` store: everything committed
+        /// with plain `commit` before this call is visible to a consumer
+        /// that observes the flag (the close handshake relies on it). A
+        /// producer batching through an `AutoCommitter` must close via
+        /// the committer's `close`, which flushes the pending batch first.
+        pub fn close(self: *Self) void {
+            self.closed.store(true, .release);
+        }
+
+        /// Reset to empty for single-threaded reuse (tests, pooled
+        /// rings) without reconstructing the possibly-large buffer.
+        /// Zeroes every cursor and cache and reopens the ring — a cleared
+        /// ring is as good as new, `closed` included. For element types
+        /// that own resources run `deinitItems` first; `clear` abandons
+        /// live slots without touching their contents. Must not race any
+        /// producer or consumer.
+        pub fn clear(self: *Self) void {
+            self.tail.store(0, .monotonic);
+            self.head.store(0, .monotonic);
+            self.shared_claim.store(0, .monotonic);
+            self.cached_head = 0;
+            self.cached_tail = 0;
+            self.reserved = 0;
+            if (config.enable_metrics) self.last_consume_ns.store(0, .monotonic);
+            self.closed.store(false, .monotonic);
+        }
+
+        /// Release all unconsumed items for element types that own
+        /// resources: walks `[head, tail)`, calls `deinitFn` on each live
+        /// element, then advances head past them. Call from the consumer
+        /// side only, after producers have stopped committing.
+        pub fn deinitItems(self: *Self, comptime deinitFn: fn (*T) void) void {
+            const head = self.head.load(.monotonic);
+            const tail = self.tail.load(.acquire);
+            var pos = head;
+            while (pos != tail) : (pos +%= 1) {
+                deinitFn(&self.buffer[pos & MASK]);
+            }
+            self.head.store(tail, .release);
+            self.cached_tail = tail;
+        }
+
+        pub fn getMetrics(self: *const Self) Metrics {
+            if (config.enable_metrics) {
+                return self.metrics;
+            }
+            return .{};
+        }
+    };
+}
+
+// ============================================================================
+// MPMC RING - Vyukov bounded queue (per-slot sequence numbers)
+// ============================================================================
+
+/// Bounded lock-free MPMC queue (Vyukov's algorithm). Instead of shared
+/// head/tail cursors with an ownership contract, every slot carries its own
+/// sequence word: producers and consumers CAS a global position, then
+/// synchronize through the claimed slot alone — any number of threads on
+/// either side. Per-op cost is a CAS plus a slot round-trip, well above the
+/// SPSC ring's plain stores, so prefer the ring-decomposed channel whenever
+/// producers can register; this is for true many-to-many topologies.
+///
+/// Seed with `init`/`create` before first use — the sequence words start
+/// at their slot index, which is what marks every slot empty.
+pub fn MpmcRing(comptime T: type, comptime config: Config) type {
+    const CAPACITY = @as(usize, 1) << config.ring_bits;
+    const MASK = CAPACITY - 1;
+    comptime config.validate();
+
+    return struct {
+        const Self = @This();
+
+        const Slot = struct {
+            sequence: std.atomic.Value(u64),
+            value: T,
+        };
+
+        // The two global positions get the same 128-byte isolation as the
+        // SPSC cursors; the slots themselves carry the per-slot traffic.
+        enqueue_pos: std.atomic.Value(u64) align(128) = std.atomic.Value(u64).init(0),
+        dequeue_pos: std.atomic.Value(u64) align(128) = std.atomic.Value(u64).init(0),
+        slots: [CAPACITY]Slot align(64) = undefined,
+
+        pub fn capacity() usize {
+            return CAPACITY;
+        }
+
+        pub fn init() Self {
+            var self = Self{};
+            self.reset();
+            return self;
+        }
+
+        /// Allocator-backed construction for capacities too large for the
+        /// stack; pair with `destroy`.
+        pub fn create(allocator: std.mem.Allocator) !*Self {
+            const self = try allocator.create(Self);
+            self.* = .{};
+            self.reset();
+            return self;
+        }
+
+        pub fn destroy(self: *Self, allocator: std.mem.Allocator) void {
+            allocator.destroy(self);
+        }
+
+        /// Seed (or reseed) the queue to empty. Must not race any
+        /// producer or consumer.
+        pub fn reset(self: *Self) void {
+            self.enqueue_pos.store(0, .monotonic);
+            self.dequeue_pos.store(0, .monotonic);
+            for (&self.slots, 0..) |*slot, i| {
+                slot.sequence.store(i, .monotonic);
+            }
+        }
+
+        /// Approximate occupancy (racy by nature under concurrency).
+        pub fn len(self: *const Self) usize {
+            const e = self.enqueue_pos.load(.monotonic);
+            const d = self.dequeue_pos.load(.monotonic);
+            return @intCast(e -% d);
+        }
+
+        /// Returns false when the queue is full.
+        pub fn enqueue(self: *Self, value: T) bool {
+            var pos = self.enqueue_pos.load(.monotonic);
+            while (true) {
+                const slot = &self.slots[pos & MASK];
+                const seq = slot.sequence.load(.acquire);
+                const diff = @as(i64, @bitCast(seq -% pos));
+
+                if (diff == 0) {
+                    // Slot is empty at our position: claim it
+                    if (self.enqueue_pos.cmpxchgWeak(pos, pos +% 1, .monotonic, .monotonic)) |actual| {
+                        pos = actual; // lost the race, retry at the new position
+                    } else {
+                        slot.value = value;
+                        slot.sequence.store(pos +% 1, .release);
+                        return true;
+                    }
+                } else if (diff < 0) {
+                    // Slot still holds an unconsumed value a lap behind: full
+                    return false;
+                } else {
+                    // Another producer advanced past us; catch up
+                    pos = self.enqueue_pos.load(.monotonic);
+                }
+            }
+        }
+
+        /// Returns null when the queue is empty.
+        pub fn dequeue(self: *Self) ?T {
+            var pos = self.dequeue_pos.load(.monotonic);
+            while (true) {
+                const slot = &self.slots[pos & MASK];
+                const seq = slot.sequence.load(.acquire);
+                const diff = @as(i64, @bitCast(seq -% (pos +% 1)));
+
+                if (diff == 0) {
+                    // Slot holds a value at our position: claim it
+                    if (self.dequeue_pos.cmpxchgWeak(pos, pos +% 1, .monotonic, .monotonic)) |actual| {
+                        pos = actual;
+                    } else {
+                        const value = slot.value;
+                        // Mark empty for the producer one lap ahead
+                        slot.sequence.store(pos +% CAPACITY, .release);
+                        return value;
+                    }
+                } else if (diff < 0) {
+                    return null; // not produced yet: empty
+                } else {
+                    pos = self.dequeue_pos.load(.monotonic);
+                }
+            }
+        }
+    };
+}
+
+// ============================================================================
+// FRAMED BYTE RING - length-prefixed records over Ring(u8)
+// ============================================================================
+
+/// Length-prefixed framing over a byte ring: each record is a little-endian
+/// u32 length followed by the payload. `writeFrame` is all-or-nothing and
+/// `readFrame` hands out only complete frames, so a partial frame never
+/// crosses the API — including frames that straddle the wraparound, which
+/// the wrapper reassembles internally.
+pub fn FramedRing(comptime config: Config) type {
+    return struct {
+        const Self = @This();
+
+        pub const ByteRing = Ring(u8, config);
+        const Cursor = ByteRing.Cursor;
+        const PREFIX = @sizeOf(u32);
+
+        ring: ByteRing = .{},
+
+        /// Write one frame (prefix + payload) as a single commit. Returns
+        /// false without writing anything if the whole frame does not fit
+        /// right now — or never can, when it exceeds the ring capacity.
+        pub fn writeFrame(self: *Self, payload: []const u8) bool {
+            const total = PREFIX + payload.len;
+            if (total > ByteRing.capacity()) return false;
+            // An outstanding tracked grant on the underlying ring would
+            // overlap the frame's region — and be clobbered below.
+            std.debug.assert(self.ring.reserved == 0);
+
+            const tail = self.ring.tail.load(.monotonic);
+            self.ring.cached_head = self.ring.head.load(.acquire);
+            if (ByteRing.capacity() - @as(usize, @intCast(tail -% self.ring.cached_head)) < total) return false;
+
+            var prefix: [PREFIX]u8 = undefined;
+            std.mem.writeInt(u32, &prefix, @intCast(payload.len), .little);
+            self.copyIn(tail, &prefix);
+            self.copyIn(tail +% PREFIX, payload);
+            self.ring.reserved = total; // record the grant for commit's check
+            self.ring.commit(total);
+            return true;
+        }
+
+        /// Append one complete frame's payload to `out` and return its
+        /// length, or null if no complete frame is queued yet. A prefix
+        /// whose payload has not fully arrived is left untouched.
+        pub fn readFrame(self: *Self, out: *std.ArrayList(u8)) !?usize {
+            const head = self.ring.head.load(.monotonic);
+            const tail = self.ring.tail.load(.acquire);
+            const avail: usize = @intCast(tail -% head);
+            if (avail < PREFIX) return null;
+
+            var prefix: [PREFIX]u8 = undefined;
+            self.copyOut(head, &prefix);
+            const frame_len = std.mem.readInt(u32, &prefix, .little);
+            if (avail < PREFIX + frame_len) return null;
+
+            try out.ensureUnusedCapacity(frame_len);
+            const start = out.items.len;
+            out.items.len += frame_len;
+            self.copyOut(head +% PREFIX, out.items[start..]);
+            self.ring.advance(PREFIX + frame_len);
+            return frame_len;
+        }
+
+        // Bulk copy to/from a free-running cursor position, split across
+        // the wrap into at most two memcpys
+        fn copyIn(self: *Self, pos: Cursor, bytes: []const u8) void {
+            const idx: usize = @intCast(pos & @as(Cursor, @intCast(ByteRing.mask())));
+            const first = @min(bytes.len, ByteRing.capacity() - idx);
+            @memcpy(self.ring.buffer[idx..][0..first], bytes[0..first]);
+            @memcpy(self.ring.buffer[0 .. bytes.len - first], bytes[first..]);
+        }
+
+        fn copyOut(self: *Self, pos: Cursor, out: []u8) void {
+            const idx: usize = @intCast(pos & @as(Cursor, @intCast(ByteRing.mask())));
+            const first = @min(out.len, ByteRing.capacity() - idx);
+            @memcpy(out[0..first], self.ring.buffer[idx..][0..first]);
+            @memcpy(out[first..], self.ring.buffer[0 .. out.len - first]);
+        }
+    };
+}
+
+// ============================================================================
+// MPSC CHANNEL - Multiple Producers, Single Consumer
+// ============================================================================
+
+/// Sharing model: a `*Channel` is the handle. The struct never moves after
+/// init (rings hand out interior pointers), so pass the same pointer to
+/// every producer thread and let each call `register` — no reference
+/// counting or outer wrapper needed, whether the channel is a file-scope
+/// global or `create`d from an allocator that outlives the threads.
+pub fn Channel(comptime T: type, comptime config: Config) type {
+    comptime config.validate();
+    const RingType = Ring(T, config);
+
+    return struct {
+        const Self = @This();
+
+        // Storage layout: the rings are one contiguous array, not
+        // per-ring heap allocations, so the consumer's round-robin sweep
+        // walks control blocks at a fixed stride with no pointer chasing —
+        // one allocation, one TLB neighborhood. Each ring's buffer rides
+        // embedded between the control blocks; the 128-byte alignment on
+        // the hot fields keeps adjacent rings off each other's lines (see
+        // the adjacent-rings test). The stride cost of the embedded
+        // buffers is the trade for never touching an allocator after init.
+        rings: [config.max_producers]RingType = [_]RingType{.{}} ** config.max_producers,
+        producer_count: std.atomic.Value(usize) = std.atomic.Value(usize).init(0),
+        closed: std.atomic.Value(bool) = std.atomic.Value(bool).init(false),
+        // Advisory core hints from registerOn (cold; consumer-side setup only)
+        cores: [config.max_producers]?usize = [_]?usize{null} ** config.max_producers,
+        // Diagnostic labels from registerNamed (cold; not owned — the
+        // caller keeps the bytes alive, typically string literals)
+        names: [config.max_producers]?[]const u8 = [_]?[]const u8{null} ** config.max_producers,
+
+        pub const Producer = struct {
+            ring: *RingType,
+            /// Ring index this producer writes to; pass it to `getRing` to
+            /// pair a consumer (or log line) with this producer's ring.
+            id: usize,
+
+            pub inline fn reserve(self: Producer, n: usize) ?Reservation(T) {
+                return self.ring.reserve(n);
+            }
+
+            pub inline fn reserveWithBackoff(self: Producer, n: usize) ?Reservation(T) {
+                return self.ring.reserveWithBackoff(n);
+            }
+
+            pub inline fn commit(self: Producer, n: usize) void {
+                self.ring.commit(n);
+            }
+
+            pub inline fn send(self: Producer, items: []const T) usize {
+                return self.ring.send(items);
+            }
+
+            pub inline fn sendWith(self: Producer, value: T, on_full: OnFull) SendOutcome {
+                return self.ring.sendWith(value, on_full);
+            }
+
+            pub inline fn flush(self: Producer) void {
+                self.ring.flush();
+            }
+        };
+
+        pub fn init() Self {
+            return .{};
+        }
+
+        /// Allocate a channel with a caller-supplied allocator; the struct
+        /// is O(max_producers × capacity) so heap placement is the norm.
+        /// Pair with `destroy`.
+        pub fn create(allocator: std.mem.Allocator) !*Self {
+            const self = try allocator.create(Self);
+            self.* = .{};
+            return self;
+        }
+
+        pub fn destroy(self: *Self, allocator: std.mem.Allocator) void {
+            allocator.destroy(self);
+        }
+
+        /// Claim a dedicated ring for the calling thread. Fails with
+        /// `error.Closed` once `close` has run, so a thread racing channel
+        /// teardown learns immediately instead of getting a producer whose
+        /// writes go nowhere.
+        pub fn register(self: *Self) error{ TooManyProducers, Closed }!Producer {
+            if (self.closed.load(.acquire)) return error.Closed;
+
+            const id = self.producer_count.fetchAdd(1, .monotonic);
+            if (id >= config.max_producers) {
+                _ = self.producer_count.fetchSub(1, .monotonic);
+                return error.TooManyProducers;
+            }
+
+            self.rings[id].active.store(true, .release);
+            return .{ .ring = &self.rings[id], .id = id };
+        }
+
+        /// `register` that also records the core this producer is (or will
+        /// be) pinned to. Purely advisory — the channel pins nothing — but
+        /// it lets consumer-side setup co-locate with its producer
+        /// (sibling hyperthread, same socket) instead of hardcoding the
+        /// layout the way the benchmarks do.
+        pub fn registerOn(self: *Self, core: usize) error{ TooManyProducers, Closed }!Producer {
+            const p = try self.register();
+            self.cores[p.id] = core;
+            return p;
+        }
+
+        /// One pre-split end pair per ring; see `splitAll`.
+        pub const SplitEnds = struct {
+            producers: [config.max_producers]RingType.ProducerEnd,
+            consumers: [config.max_producers]RingType.ConsumerEnd,
+        };
+
+        /// The channel-wide version of `RingType.split`: claims all
+        /// `max_producers` slots up front and hands back one producer end
+        /// and one consumer end per ring. Pair i owns the two halves of
+        /// ring i, so each ring keeps the SPSC discipline in the types
+        /// while one thread holds every consumer end for the MPSC fan-in.
+        /// An alternative to `register` for topologies fixed at startup —
+        /// call once on a fresh channel before any traffic, then
+        /// distribute the ends; there is no registration race left for
+        /// `awaitProducers` to wait out. A producer done for good closes
+        /// its ring through its end; the matching consumer end sees it
+        /// via `isClosed`.
+        pub fn splitAll(self: *Self) SplitEnds {
+            std.debug.assert(self.producer_count.load(.monotonic) == 0);
+
+            var ends: SplitEnds = undefined;
+            for (0..config.max_producers) |i| {
+                self.rings[i].active.store(true, .release);
+                ends.producers[i] = .{ .ring = &self.rings[i] };
+                ends.consumers[i] = .{ .ring = &self.rings[i] };
+            }
+            self.producer_count.store(config.max_producers, .release);
+            return ends;
+        }
+
+        /// Core hint recorded by `registerOn`; null when the producer
+        /// registered without one.
+        pub fn producerCore(self: *const Self, id: usize) ?usize {
+            std.debug.assert(id < config.max_producers);
+            return self.cores[id];
+        }
+
+        /// `register` that also attaches a diagnostic label to the ring,
+        /// so monitoring reads "orders" or "cancels" instead of "ring 3".
+        /// The channel does not copy the bytes — pass a literal or
+        /// something that outlives the channel.
+        pub fn registerNamed(self: *Self, name: []const u8) error{ TooManyProducers, Closed }!Producer {
+            const p = try self.register();
+            self.names[p.id] = name;
+            return p;
+        }
+
+        /// Label recorded by `registerNamed`; null when the producer
+        /// registered without one. Stitch it into log lines and metric
+        /// dumps next to the numeric id.
+        pub fn producerName(self: *const Self, id: usize) ?[]const u8 {
+            std.debug.assert(id < config.max_producers);
+            return self.names[id];
+        }
+
+        /// Round-robin receive from all active producers
+        pub fn recv(self: *Self, out: []T) usize {
+            var total: usize = 0;
+            const count = self.producer_count.load(.acquire);
+            for (self.rings[0..count]) |*ring| {
+                if (total >= out.len) break;
+                total += ring.recv(out[total..]);
+            }
+            return total;
+        }
+
+        /// Ring behind a producer id (as carried in `Producer.id`), for
+        /// pairing a consumer with a specific producer's ring.
+        pub fn getRing(self: *Self, id: usize) *RingType {
+            std.debug.assert(id < self.producer_count.load(.acquire));
+            return &self.rings[id];
+        }
+
+        /// Visit every registered ring with its id, for user-written
+        /// cross-ring maintenance (health checks, snapshot export, ...).
+        /// The visitor needs `pub fn visit(self, id: usize, ring: *RingType)`.
+        pub fn forEachRing(self: *Self, visitor: anytype) void {
+            const count = self.producer_count.load(.acquire);
+            for (0..count) |i| {
+                visitor.visit(i, &self.rings[i]);
+            }
+        }
+
+        /// Batch consume from all producers - THE FAST PATH
+        pub fn consumeAll(self: *Self, handler: anytype) usize {
+            var total: usize = 0;
+            const count = self.producer_count.load(.acquire);
+            for (0..count) |i| {
+                total += self.rings[i].consumeBatch(handler);
+            }
+            return total;
+        }
+
+        /// Consume up to max_total items from all producers, preferring earlier rings.
+        /// Useful for real-world processing to limit batch size and avoid long pauses.
+        pub fn consumeAllUpTo(self: *Self, max_total: usize, handler: anytype) usize {
+            var total: usize = 0;
+            const count = self.producer_count.load(.acquire);
+            for (0..count) |i| {
+                if (total >= max_total) break;
+                const remaining = max_total - total;
+                total += self.rings[i].consumeUpTo(remaining, handler);
+            }
+            return total;
+        }
+
+        /// Shutdown flush: fully drain every ring in one call, handing
+        /// each item to the handler with its producer id
+        /// (`pub fn process(self, id: usize, item: *const T)`). Each ring
+        /// is drained until empty, so commits racing the drain are caught
+        /// too. Returns the total drained — the "flush everything before
+        /// exit" a supervisor runs after `close`.
+        pub fn drainAll(self: *Self, handler: anytype) usize {
+            var total: usize = 0;
+            const count = self.producer_count.load(.acquire);
+            for (0..count) |i| {
+                const Tagged = struct {
+                    inner: @TypeOf(handler),
+                    id: usize,
+                    pub fn process(a: @This(), item: *const T) void {
+                        a.inner.process(a.id, item);
+                    }
+                };
+                while (true) {
+                    const n = self.rings[i].consumeBatch(Tagged{ .inner = handler, .id = i });
+                    if (n == 0) break;
+                    total += n;
+                }
+            }
+            return total;
+        }
+
+        /// Drain rings in caller-chosen priority order — e.g. the cancel
+        /// producer's ring before the order-entry rings. Each sweep visits
+        /// the ids in `order`, taking up to `per_ring_cap` items per ring;
+        /// the cap bounds how long a busy high-priority ring can starve
+        /// the rest. Ids at or past the registered count are skipped, so a
+        /// static priority list works before every producer has
+        /// registered. Returns the total items consumed this sweep.
+        pub fn drainPrioritized(self: *Self, order: []const usize, per_ring_cap: usize, handler: anytype) usize {
+            var total: usize = 0;
+            const count = self.producer_count.load(.acquire);
+            for (order) |id| {
+                if (id >= count) continue;
+                total += self.rings[id].consumeUpTo(per_ring_cap, handler);
+            }
+            return total;
+        }
+
+        pub fn close(self: *Self) void {
+            self.closed.store(true, .release);
+            const count = self.producer_count.load(.acquire);
+            for (self.rings[0..count]) |*ring| {
+                ring.close();
+            }
+        }
+
+        pub fn isClosed(self: *const Self) bool {
+            return self.closed.load(.acquire);
+        }
+
+        /// Producers ever registered (slots handed out). Monotonic; see
+        /// `activeProducers` for the currently-live count.
+        pub fn producerCount(self: *const Self) usize {
+            return self.producer_count.load(.acquire);
+        }
+
+        /// Spin (with backoff) until `expected` producers have registered
+        /// or `timeout_ns` elapses; true when the count was reached. This
+        /// closes the startup race where the consumer begins its sweep
+        /// before late producers have grabbed rings and under-services
+        /// them — wait here, then size the sweep off `producerCount`.
+        /// Registration is quick, so this spins rather than parks; size
+        /// the timeout accordingly.
+        pub fn awaitProducers(self: *const Self, expected: usize, timeout_ns: u64) bool {
+            std.debug.assert(expected <= config.max_producers);
+            var backoff = Backoff{};
+            const t0 = std.time.Instant.now() catch unreachable;
+            while (self.producer_count.load(.acquire) < expected) {
+                if ((std.time.Instant.now() catch unreachable).since(t0) >= timeout_ns) {
+                    return self.producer_count.load(.acquire) >= expected;
+                }
+                backoff.snooze();
+            }
+            return true;
+        }
+
+        /// Producers whose ring is currently live (`active` flag set).
+        /// Supervisory logic can poll this to decide when a consumer may
+        /// retire; it only diverges from `producerCount` once producers
+        /// mark their ring inactive on the way out.
+        pub fn activeProducers(self: *const Self) usize {
+            var n: usize = 0;
+            const count = self.producer_count.load(.acquire);
+            for (self.rings[0..count]) |*ring| {
+                if (ring.active.load(.acquire)) n += 1;
+            }
+            return n;
+        }
+
+        /// Total memory cost of the channel in bytes: all
+        /// `max_producers` rings (active or not) plus channel bookkeeping.
+        /// Comptime-callable, for ops dashboards and capacity planning.
+        pub fn totalFootprint() usize {
+            return @sizeOf(Self);
+        }
+
+        pub fn getMetrics(self: *const Self) Metrics {
+            var m = Metrics{};
+            const count = self.producer_count.load(.acquire);
+            for (self.rings[0..count]) |*ring| {
+                const rm = ring.getMetrics();
+                m.messages_sent += rm.messages_sent;
+                m.messages_received += rm.messages_received;
+                m.batches_sent += rm.batches_sent;
+                m.batches_received += rm.batches_received;
+                m.messages_dropped += rm.messages_dropped;
+                m.producer_cache_refreshes += rm.producer_cache_refreshes;
+                m.consumer_cache_refreshes += rm.consumer_cache_refreshes;
+            }
+            return m;
+        }
+    };
+}
+
+// Type aliases
+pub const DefaultRing = Ring(u64, default_config);
+pub const DefaultChannel = Channel(u64, default_config);
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+test "config: withCapacity rounds up to the next power of two" {
+    try std.testing.expectEqual(@as(u6, 14), default_config.withCapacity(10_000).ring_bits);
+    try std.testing.expectEqual(@as(u6, 4), default_config.withCapacity(16).ring_bits);
+    try std.testing.expectEqual(@as(u6, 0), default_config.withCapacity(1).ring_bits);
+
+    const R = Ring(u64, default_config.withCapacity(10_000));
+    try std.testing.expectEqual(@as(usize, 16384), R.capacity());
+}
+
+var global_ring = Ring(u32, Config{ .ring_bits = 4 }){};
+
+test "config: usable as a hash-map key for channel pooling" {
+    const a = Config{ .ring_bits = 12 };
+    const b = Config{ .ring_bits = 12 };
+    const c = Config{ .ring_bits = 12, .enable_metrics = true };
+
+    try std.testing.expect(a.eql(b));
+    try std.testing.expectEqual(a.hash(), b.hash());
+    try std.testing.expect(!a.eql(c));
+    try std.testing.expect(a.hash() != c.hash());
+
+    // The point of the exercise: dedup channel creation by config
+    var pool = std.AutoHashMap(Config, usize).init(std.testing.allocator);
+    defer pool.deinit();
+    try pool.put(a, 1);
+    try pool.put(c, 2);
+    try std.testing.expectEqual(@as(?usize, 1), pool.get(b));
+    try std.testing.expectEqual(@as(?usize, 2), pool.get(c));
+}
+
+test "ring: usable as a file-scope global" {
+    _ = global_ring.send(&[_]u32{ 5, 6, 7 });
+
+    var out: [4]u32 = undefined;
+    const n = global_ring.recv(&out);
+    try std.testing.expectEqual(@as(usize, 3), n);
+    try std.testing.expectEqual(@as(u32, 5), out[0]);
+    try std.testing.expect(global_ring.isEmpty());
+}
+
+test "ring: basic reserve/commit/readable/advance" {
+    var ring = Ring(u64, default_config){};
+
+    // Write
+    const w = ring.reserve(4).?;
+    w.slice[0] = 100;
+    w.slice[1] = 200;
+    w.slice[2] = 300;
+    w.slice[3] = 400;
+    ring.commit(4);
+
+    try std.testing.expectEqual(@as(usize, 4), ring.len());
+
+    // Read
+    const r = ring.readable().?;
+    try std.testing.expectEqual(@as(u64, 100), r[0]);
+    try std.testing.expectEqual(@as(u64, 400), r[3]);
+    ring.advance(4);
+
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: push/pop one-shot round-trip" {
+    var ring = Ring(u64, Config{ .ring_bits = 2 }){}; // 4 slots
+
+    try std.testing.expect(ring.pop() == null);
+
+    for (0..4) |i| try std.testing.expect(ring.push(i));
+    try std.testing.expect(!ring.push(99)); // full: value refused
+
+    for (0..4) |i| try std.testing.expectEqual(@as(?u64, i), ring.pop());
+    try std.testing.expect(ring.pop() == null);
+
+    // Freed space is usable again
+    try std.testing.expect(ring.push(7));
+    try std.testing.expectEqual(@as(?u64, 7), ring.pop());
+}
+
+test "ring: peekSlice on empty and non-empty ring" {
+    var ring = Ring(u64, default_config){};
+
+    try std.testing.expectEqual(@as(usize, 0), ring.peekSlice().len);
+
+    const w = ring.reserve(2).?;
+    w.slice[0] = 7;
+    w.slice[1] = 8;
+    ring.commit(2);
+
+    const s = ring.peekSlice();
+    try std.testing.expectEqual(@as(usize, 2), s.len);
+    try std.testing.expectEqual(@as(u64, 7), s[0]);
+    try std.testing.expectEqual(@as(usize, 2), ring.len()); // not consumed
+}
+
+test "ring: peekSpin bounds its spinning and sees data immediately" {
+    var ring = Ring(u64, default_config){};
+
+    // Empty ring: gives up after the bounded spin
+    try std.testing.expectEqual(@as(usize, 0), ring.peekSpin(16).len);
+
+    _ = ring.send(&[_]u64{ 7, 8 });
+    const slice = ring.peekSpin(16);
+    try std.testing.expectEqual(@as(usize, 2), slice.len);
+    try std.testing.expectEqual(@as(u64, 7), slice[0]);
+}
+
+test "ring: readIndex and distanceToWrap locate the wrap boundary" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    try std.testing.expectEqual(@as(usize, 0), ring.readIndex());
+    try std.testing.expectEqual(@as(usize, 8), ring.distanceToWrap());
+
+    _ = ring.send(&[_]u64{ 0, 0, 0, 0, 0, 0 });
+    ring.advance(6);
+    try std.testing.expectEqual(@as(usize, 6), ring.readIndex());
+    try std.testing.expectEqual(@as(usize, 2), ring.distanceToWrap());
+
+    // The contiguous run really is min(len, distanceToWrap)
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4 });
+    try std.testing.expectEqual(
+        @min(ring.len(), ring.distanceToWrap()),
+        ring.peekSlice().len,
+    );
+}
+
+test "ring: peekBoth returns pre- and post-wrap runs" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    // No wrap yet: everything in the first segment
+    _ = ring.send(&[_]u64{ 1, 2, 3 });
+    var s = ring.peekBoth();
+    try std.testing.expectEqual(@as(usize, 3), s.first.len);
+    try std.testing.expectEqual(@as(usize, 0), s.second.len);
+    ring.advance(3);
+
+    // Force data across the wrap boundary: slots 3..8 then 0..1
+    for (0..7) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i + 10);
+        ring.commit(1);
+    }
+    s = ring.peekBoth();
+    try std.testing.expectEqual(@as(usize, 5), s.first.len);
+    try std.testing.expectEqual(@as(usize, 2), s.second.len);
+    try std.testing.expectEqual(@as(u64, 10), s.first[0]);
+    try std.testing.expectEqual(@as(u64, 15), s.second[0]);
+    ring.advance(s.first.len + s.second.len);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: peekInto snapshots the front without consuming" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    // Park the cursors so the readable run straddles the wrap: 5 + 2
+    _ = ring.send(&[_]u64{ 0, 0, 0 });
+    ring.advance(3);
+    for (0..7) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i + 10);
+        ring.commit(1);
+    }
+
+    var scratch: [16]u64 = undefined;
+    const n = ring.peekInto(&scratch);
+    try std.testing.expectEqual(@as(usize, 7), n);
+    for (scratch[0..n], 0..) |v, i| try std.testing.expectEqual(@as(u64, i + 10), v);
+    try std.testing.expectEqual(@as(usize, 7), ring.len()); // head unchanged
+
+    // A short destination clips the copy; accept a prefix and advance it
+    var two: [2]u64 = undefined;
+    try std.testing.expectEqual(@as(usize, 2), ring.peekInto(&two));
+    try std.testing.expectEqual(@as(u64, 10), two[0]);
+    ring.advance(2);
+    try std.testing.expectEqual(@as(usize, 5), ring.peekInto(&scratch));
+    try std.testing.expectEqual(@as(u64, 12), scratch[0]);
+}
+
+test "ring: peek iterator does not consume" {
+    var ring = Ring(u64, default_config){};
+
+    const w = ring.reserve(3).?;
+    w.slice[0] = 1;
+    w.slice[1] = 2;
+    w.slice[2] = 3;
+    ring.commit(3);
+
+    var it = ring.peekIter();
+    var sum: u64 = 0;
+    while (it.next()) |item| sum += item.*;
+
+    try std.testing.expectEqual(@as(u64, 6), sum);
+    try std.testing.expectEqual(@as(usize, 3), ring.len()); // head unchanged
+
+    // Items committed after the snapshot are not yielded
+    var it2 = ring.peekIter();
+    const w2 = ring.reserve(1).?;
+    w2.slice[0] = 4;
+    ring.commit(1);
+    var seen: usize = 0;
+    while (it2.next()) |_| seen += 1;
+    try std.testing.expectEqual(@as(usize, 3), seen);
+}
+
+test "ring: batch consumption" {
+    var ring = Ring(u64, default_config){};
+
+    // Write 10 items
+    for (0..10) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i * 10);
+        ring.commit(1);
+    }
+
+    // Consume all at once
+    var sum: u64 = 0;
+    const Handler = struct {
+        sum: *u64,
+        pub fn process(self: @This(), item: *const u64) void {
+            self.sum.* += item.*;
+        }
+    };
+    const consumed = ring.consumeBatch(Handler{ .sum = &sum });
+
+    try std.testing.expectEqual(@as(usize, 10), consumed);
+    try std.testing.expectEqual(@as(u64, 0 + 10 + 20 + 30 + 40 + 50 + 60 + 70 + 80 + 90), sum);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: consumeFold reduces a batch to a summary" {
+    var ring = Ring(u64, Config{ .ring_bits = 4 }){};
+
+    const Stats = struct { sum: u64, max: u64 };
+    const Folder = struct {
+        pub fn fold(_: @This(), acc: Stats, item: *const u64) Stats {
+            return .{ .sum = acc.sum + item.*, .max = @max(acc.max, item.*) };
+        }
+    };
+
+    // Empty: init comes back untouched
+    const empty = ring.consumeFold(Stats, .{ .sum = 0, .max = 0 }, Folder{});
+    try std.testing.expectEqual(@as(usize, 0), empty.count);
+    try std.testing.expectEqual(@as(u64, 0), empty.acc.sum);
+
+    _ = ring.send(&[_]u64{ 3, 9, 4, 1 });
+    const r = ring.consumeFold(Stats, .{ .sum = 0, .max = 0 }, Folder{});
+    try std.testing.expectEqual(@as(usize, 4), r.count);
+    try std.testing.expectEqual(@as(u64, 17), r.acc.sum);
+    try std.testing.expectEqual(@as(u64, 9), r.acc.max);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: consumeBatchGreedy follows new data across snapshots" {
+    const R = Ring(u64, default_config);
+    var ring = R{};
+    _ = ring.send(&[_]u64{ 1, 2 });
+
+    // Handler that keeps producing for a while, so each drained batch
+    // leaves fresh data behind the tail snapshot
+    const Refill = struct {
+        ring: *R,
+        left: *usize,
+        sum: *u64,
+        pub fn process(self: @This(), item: *const u64) void {
+            self.sum.* += item.*;
+            if (self.left.* > 0) {
+                self.left.* -= 1;
+                _ = self.ring.send(&[_]u64{10});
+            }
+        }
+    };
+    var left: usize = 3;
+    var sum: u64 = 0;
+    const h = Refill{ .ring = &ring, .left = &left, .sum = &sum };
+
+    const total = ring.consumeBatchGreedy(h, 8);
+    try std.testing.expectEqual(@as(usize, 5), total);
+    try std.testing.expectEqual(@as(u64, 1 + 2 + 3 * 10), sum);
+    try std.testing.expect(ring.isEmpty());
+
+    // The round cap is a hard bound
+    _ = ring.send(&[_]u64{9});
+    try std.testing.expectEqual(@as(usize, 0), ring.consumeBatchGreedy(h, 0));
+}
+
+test "ring: split ends expose one side of the contract each" {
+    var ring = Ring(u64, default_config){};
+    const ends = ring.split();
+
+    const w = ends.producer.reserve(3).?;
+    w.slice[0] = 1;
+    w.slice[1] = 2;
+    w.slice[2] = 3;
+    ends.producer.commit(3);
+
+    const slice = ends.consumer.peekSlice();
+    try std.testing.expectEqual(@as(usize, 3), slice.len);
+    ends.consumer.advance(1);
+
+    var out: [4]u64 = undefined;
+    try std.testing.expectEqual(@as(usize, 2), ends.consumer.recv(&out));
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 2, 3 }, out[0..2]);
+}
+
+test "ring: close handshake drains a full ring closed by the producer" {
+    const R = Ring(u64, Config{ .ring_bits = 4 });
+    var ring = R{};
+
+    // Producer fills the ring to the brim, then closes and goes away
+    for (0..R.capacity()) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = i;
+        ring.commit(1);
+    }
+    ring.close();
+
+    var count: u64 = 0;
+    const Handler = struct {
+        count: *u64,
+        pub fn process(self: @This(), item: *const u64) void {
+            _ = item;
+            self.count.* += 1;
+        }
+    };
+    try std.testing.expectEqual(R.capacity(), ring.consumeUntilClosed(Handler{ .count = &count }));
+    try std.testing.expectEqual(@as(u64, R.capacity()), count);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: close handshake races a live producer without losing messages" {
+    const MSG: u64 = 100_000;
+    const R = Ring(u64, Config{ .ring_bits = 8 });
+
+    const ring = try R.create(std.testing.allocator);
+    defer ring.destroy(std.testing.allocator);
+
+    const producer = struct {
+        fn run(r: *R) void {
+            var i: u64 = 0;
+            while (i < MSG) {
+                if (r.reserve(1)) |w| {
+                    w.slice[0] = i;
+                    r.commit(1);
+                    i += 1;
+                } else std.atomic.spinLoopHint();
+            }
+            r.close();
+        }
+    };
+    const t = try std.Thread.spawn(.{}, producer.run, .{ring});
+
+    var count: u64 = 0;
+    const Handler = struct {
+        count: *u64,
+        pub fn process(self: @This(), item: *const u64) void {
+            _ = item;
+            self.count.* += 1;
+        }
+    };
+    const total = ring.consumeUntilClosed(Handler{ .count = &count });
+    t.join();
+
+    try std.testing.expectEqual(@as(usize, MSG), total);
+    try std.testing.expectEqual(MSG, count);
+}
+
+test "ring: timeSinceLastConsume flags a consumer that stopped draining" {
+    var ring = Ring(u64, Config{ .ring_bits = 4, .enable_metrics = true }){};
+
+    // No advance yet: no stamp, watchdog sees 0
+    try std.testing.expectEqual(@as(u64, 0), ring.timeSinceLastConsume());
+
+    _ = ring.send(&[_]u64{ 1, 2, 3 });
+    ring.advance(3);
+
+    // Let a measurable stall accumulate
+    const t0 = std.time.Instant.now() catch unreachable;
+    while ((std.time.Instant.now() catch unreachable).since(t0) < 1000) {
+        std.atomic.spinLoopHint();
+    }
+    const stall = ring.timeSinceLastConsume();
+    try std.testing.expect(stall > 0);
+
+    // A fresh advance resets the watchdog's view
+    _ = ring.send(&[_]u64{4});
+    ring.advance(1);
+    try std.testing.expect(ring.timeSinceLastConsume() <= stall + std.time.ns_per_s);
+}
+
+test "ring: peekStatus separates open-empty from closed-and-drained" {
+    var ring = Ring(u64, Config{ .ring_bits = 4 }){};
+
+    try std.testing.expect(ring.peekStatus() == .empty);
+
+    _ = ring.send(&[_]u64{ 1, 2 });
+    switch (ring.peekStatus()) {
+        .data => |slice| try std.testing.expectEqual(@as(usize, 2), slice.len),
+        else => return error.TestUnexpectedResult,
+    }
+
+    // Closing does not hide data already in the ring
+    ring.close();
+    switch (ring.peekStatus()) {
+        .data => |slice| try std.testing.expectEqual(@as(u64, 1), slice[0]),
+        else => return error.TestUnexpectedResult,
+    }
+
+    ring.advance(2);
+    try std.testing.expect(ring.peekStatus() == .closed_empty);
+}
+
+test "ring: eventStamp changes on data and on close" {
+    var ring = Ring(u64, Config{ .ring_bits = 4 }){};
+
+    const idle = ring.eventStamp();
+    try std.testing.expectEqual(idle, ring.eventStamp()); // stable while nothing happens
+
+    // New data moves the stamp; waitEvent on the old stamp returns at once
+    _ = ring.send(&[_]u64{42});
+    const after_data = ring.eventStamp();
+    try std.testing.expect(after_data != idle);
+    try std.testing.expectEqual(after_data, ring.waitEvent(idle));
+
+    // Draining does not fire an event — only the producer side does
+    ring.advance(1);
+    try std.testing.expectEqual(after_data, ring.eventStamp());
+
+    // Close flips bit 0, so shutdown is observable with zero data in flight
+    ring.close();
+    const after_close = ring.eventStamp();
+    try std.testing.expect(after_close != after_data);
+    try std.testing.expectEqual(@as(u64, 1), after_close & 1);
+    try std.testing.expect(ring.isClosed());
+}
+
+test "ring: dwell tracking reports time-in-queue to the handler" {
+    var ring = Ring(u64, Config{ .ring_bits = 4, .track_dwell = true }){};
+    _ = ring.send(&[_]u64{ 1, 2, 3 });
+
+    // Let the items age a little, on the same clock the ring uses
+    const t0 = std.time.Instant.now() catch unreachable;
+    while ((std.time.Instant.now() catch unreachable).since(t0) < 1000) {
+        std.atomic.spinLoopHint();
+    }
+
+    var min_age: u64 = std.math.maxInt(u64);
+    var count: usize = 0;
+    const Handler = struct {
+        min_age: *u64,
+        count: *usize,
+        pub fn process(self: @This(), item: *const u64, age_ns: u64) void {
+            _ = item;
+            self.min_age.* = @min(self.min_age.*, age_ns);
+            self.count.* += 1;
+        }
+    };
+    const consumed = ring.consumeBatchTimed(Handler{ .min_age = &min_age, .count = &count });
+
+    try std.testing.expectEqual(@as(usize, 3), consumed);
+    try std.testing.expectEqual(@as(usize, 3), count);
+    try std.testing.expect(min_age >= 1000); // at least the aging spin
+}
+
+test "ring: debug canaries accept a well-behaved large payload" {
+    // 40-byte T: published purely through the head/tail pairing. The
+    // canary machinery must stay silent for contract-abiding traffic,
+    // wraparound included.
+    const Big = struct { a: u64, b: u64, c: u64, d: u64, e: u64 };
+    var ring = Ring(Big, Config{ .ring_bits = 2 }){}; // 4 slots
+
+    var checked: usize = 0;
+    const Handler = struct {
+        checked: *usize,
+        pub fn process(self: @This(), item: *const Big) void {
+            std.debug.assert(item.a == item.e);
+            self.checked.* += 1;
+        }
+    };
+
+    for (0..10) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = .{ .a = i, .b = 0, .c = 0, .d = 0, .e = i };
+        ring.commit(1);
+        _ = ring.consumeBatch(Handler{ .checked = &checked });
+    }
+    try std.testing.expectEqual(@as(usize, 10), checked);
+}
+
+test "ring: consumeBatchMut zeroizes items in place" {
+    var ring = Ring(u64, default_config){};
+    _ = ring.send(&[_]u64{ 11, 22, 33 });
+
+    var sum: u64 = 0;
+    const Scrub = struct {
+        sum: *u64,
+        pub fn process(self: @This(), item: *u64) void {
+            self.sum.* += item.*;
+            item.* = 0; // clear the slot after reading
+        }
+    };
+    try std.testing.expectEqual(@as(usize, 3), ring.consumeBatchMut(Scrub{ .sum = &sum }));
+    try std.testing.expectEqual(@as(u64, 66), sum);
+    try std.testing.expect(ring.isEmpty());
+
+    // The scrub really landed in the buffer
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 0, 0, 0 }, ring.buffer[0..3]);
+}
+
+test "ring: consume up to limit" {
+    var ring = Ring(u64, default_config){};
+
+    // Write 10 items
+    for (0..10) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i * 10);
+        ring.commit(1);
+    }
+
+    // Consume up to 5
+    var sum: u64 = 0;
+    const Handler = struct {
+        sum: *u64,
+        pub fn process(self: @This(), item: *const u64) void {
+            self.sum.* += item.*;
+        }
+    };
+    const consumed = ring.consumeUpTo(5, Handler{ .sum = &sum });
+
+    try std.testing.expectEqual(@as(usize, 5), consumed);
+    try std.testing.expectEqual(@as(u64, 0 + 10 + 20 + 30 + 40), sum);
+    try std.testing.expectEqual(@as(usize, 5), ring.len()); // 5 left
+
+    // Consume remaining
+    sum = 0;
+    const consumed2 = ring.consumeUpTo(10, Handler{ .sum = &sum });
+    try std.testing.expectEqual(@as(usize, 5), consumed2);
+    try std.testing.expectEqual(@as(u64, 50 + 60 + 70 + 80 + 90), sum);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: drainInto moves items across the wrap into a list" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    // Push the cursors forward so the next batch straddles the wrap
+    _ = ring.send(&[_]u64{ 0, 0, 0, 0, 0, 0 });
+    ring.advance(6);
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4, 5, 6, 7 });
+
+    var out = std.ArrayList(u64).init(std.testing.allocator);
+    defer out.deinit();
+
+    try std.testing.expectEqual(@as(usize, 4), try ring.drainInto(&out, 4));
+    try std.testing.expectEqual(@as(usize, 3), try ring.drainInto(&out, 16));
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 1, 2, 3, 4, 5, 6, 7 }, out.items);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: snapshot round-trips through toOwnedItems and preloaded" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    // Straddle the wrap so the checkpoint has to reorder the two runs
+    _ = ring.send(&[_]u64{ 0, 0, 0 });
+    ring.advance(3);
+    for (0..6) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i + 100);
+        ring.commit(1);
+    }
+
+    const saved = try ring.toOwnedItems(std.testing.allocator);
+    defer std.testing.allocator.free(saved);
+    try std.testing.expectEqual(@as(usize, 6), saved.len);
+    for (saved, 0..) |v, i| try std.testing.expectEqual(@as(u64, i + 100), v);
+    try std.testing.expectEqual(@as(usize, 6), ring.len()); // checkpoint did not consume
+
+    // Restore into a fresh ring and drain it in the same order
+    var restored = Ring(u64, Config{ .ring_bits = 3 }).preloaded(saved);
+    try std.testing.expectEqual(@as(usize, 6), restored.len());
+    var buf: [8]u64 = undefined;
+    const n = restored.recv(&buf);
+    try std.testing.expectEqualSlices(u64, saved, buf[0..n]);
+}
+
+test "ring: advanceTo seeks the consumer cursor to an absolute position" {
+    var ring = Ring(u64, default_config){};
+
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4, 5 });
+
+    // Checkpoint mid-stream, then seek straight past the first three
+    const s = ring.snapshot();
+    ring.advanceTo(@intCast(s.head + 3));
+    try std.testing.expectEqual(@as(usize, 2), ring.len());
+    try std.testing.expectEqual(@as(u64, 4), ring.peekSlice()[0]);
+
+    // Seeking to tail drains everything
+    ring.advanceTo(@intCast(s.tail));
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: prefetchRange tolerates any cursor and count" {
+    // Prefetch is a hint with no observable effect; the test pins down
+    // that the index math stays in bounds across the wrap and at the
+    // degenerate counts
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){};
+
+    _ = ring.send(&[_]u64{ 0, 0, 0, 0, 0, 0 });
+    ring.advance(6);
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4 }); // straddles the wrap
+
+    const s = ring.snapshot();
+    ring.prefetchRange(@intCast(s.head), 0);
+    ring.prefetchRange(@intCast(s.head), 4);
+    ring.prefetchRange(@intCast(s.head), @TypeOf(ring).capacity());
+
+    try std.testing.expectEqual(@as(usize, 4), ring.len());
+}
+
+test "ring: consumeExact is all-or-nothing" {
+    var ring = Ring(u64, default_config){};
+
+    for (0..6) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i);
+        ring.commit(1);
+    }
+
+    var sum: u64 = 0;
+    const Handler = struct {
+        sum: *u64,
+        pub fn process(self: @This(), item: *const u64) void {
+            self.sum.* += item.*;
+        }
+    };
+
+    // A full frame is not available: nothing is consumed
+    try std.testing.expect(!ring.consumeExact(8, Handler{ .sum = &sum }));
+    try std.testing.expectEqual(@as(u64, 0), sum);
+    try std.testing.expectEqual(@as(usize, 6), ring.len());
+
+    // Exactly one frame of 4, then the remainder stays queued
+    try std.testing.expect(ring.consumeExact(4, Handler{ .sum = &sum }));
+    try std.testing.expectEqual(@as(u64, 0 + 1 + 2 + 3), sum);
+    try std.testing.expectEqual(@as(usize, 2), ring.len());
+}
+
+test "ring: reserve honors the element alignment for over-aligned T" {
+    const V = @Vector(4, u64); // @alignOf = 32 on common targets
+    const R = Ring(V, Config{ .ring_bits = 3 });
+    var ring = R{};
+
+    // The buffer itself carries at least the element alignment
+    try std.testing.expect(@intFromPtr(&ring.buffer) % @alignOf(V) == 0);
+
+    // Every cursor position hands back an @alignOf(V)-aligned pointer
+    for (0..2 * R.capacity()) |_| {
+        const r = ring.reserve(1).?;
+        try std.testing.expect(@intFromPtr(r.slice.ptr) % @alignOf(V) == 0);
+        r.slice[0] = @splat(0);
+        ring.commit(1);
+        ring.advance(1);
+    }
+}
+
+test "ring: aligned reservation pads to the requested boundary" {
+    var ring = Ring(u64, default_config){};
+
+    // Misalign the tail by one 8-byte slot
+    const w = ring.reserve(1).?;
+    w.slice[0] = 0;
+    ring.commit(1);
+
+    const r = ring.reserveAligned(4, 64, 0xFFFF_FFFF_FFFF_FFFF).?;
+    try std.testing.expectEqual(@as(usize, 0), @intFromPtr(r.slice.ptr) % 64);
+    try std.testing.expectEqual(@as(usize, 7), r.pad); // 7 slots to the next 64B line
+    for (r.slice[0..4], 0..) |*slot, i| slot.* = i;
+    ring.commit(r.pad + 4);
+
+    try std.testing.expectEqual(@as(usize, 1 + 7 + 4), ring.len());
+}
+
+test "ring: produceFromReader ingests across the wrap" {
+    var ring = Ring(u8, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    // Shift the cursors so the free region wraps
+    _ = ring.send("abcde");
+    var sink: [5]u8 = undefined;
+    _ = ring.recv(&sink);
+
+    var stream = std.io.fixedBufferStream("0123456789");
+    const n = try ring.produceFromReader(stream.reader());
+    try std.testing.expectEqual(@as(usize, 8), n); // filled to capacity
+
+    var out: [8]u8 = undefined;
+    var got: usize = 0;
+    while (got < 8) got += ring.recv(out[got..]);
+    try std.testing.expectEqualStrings("01234567", &out);
+}
+
+test "ring: tracked reserve and commitAll" {
+    var ring = Ring(u64, default_config){};
+
+    const w = ring.reserveTracked(3).?;
+    w.slice[0] = 1;
+    w.slice[1] = 2;
+    w.slice[2] = 3;
+    ring.commitAll();
+
+    try std.testing.expectEqual(@as(usize, 3), ring.len());
 
-    try std.testing.expect(ring.isEmpty());
+    // A second commitAll without a new reservation commits nothing
+    ring.commitAll();
+    try std.testing.expectEqual(@as(usize, 3), ring.len());
 }
 
-test "ring: batch consumption" {
+test "ring: consumeShared claims disjoint batches" {
     var ring = Ring(u64, default_config){};
 
-    // Write 10 items
     for (0..10) |i| {
         const w = ring.reserve(1).?;
-        w.slice[0] = @intCast(i * 10);
+        w.slice[0] = @intCast(i);
         ring.commit(1);
     }
 
-    // Consume all at once
     var sum: u64 = 0;
     const Handler = struct {
         sum: *u64,
@@ -534,43 +3167,251 @@ test "ring: batch consumption" {
             self.sum.* += item.*;
         }
     };
-    const consumed = ring.consumeBatch(Handler{ .sum = &sum });
 
-    try std.testing.expectEqual(@as(usize, 10), consumed);
-    try std.testing.expectEqual(@as(u64, 0 + 10 + 20 + 30 + 40 + 50 + 60 + 70 + 80 + 90), sum);
-    try std.testing.expect(ring.isEmpty());
+    try std.testing.expectEqual(@as(usize, 4), ring.consumeShared(4, Handler{ .sum = &sum }));
+    try std.testing.expectEqual(@as(usize, 4), ring.consumeShared(4, Handler{ .sum = &sum }));
+    try std.testing.expectEqual(@as(usize, 2), ring.consumeShared(4, Handler{ .sum = &sum }));
+    try std.testing.expectEqual(@as(usize, 0), ring.consumeShared(4, Handler{ .sum = &sum }));
+    try std.testing.expectEqual(@as(u64, 45), sum);
 }
 
-test "ring: consume up to limit" {
-    var ring = Ring(u64, default_config){};
+test "ring: autoCommitter batches publication until the threshold" {
+    var ring = Ring(u64, Config{ .ring_bits = 4 }){};
+    var committer = ring.autoCommitter(4);
 
-    // Write 10 items
-    for (0..10) |i| {
-        const w = ring.reserve(1).?;
-        w.slice[0] = @intCast(i * 10);
-        ring.commit(1);
+    // Three singles stay below the threshold: staged locally, and the
+    // shared tail — what the consumer sees — hasn't moved at all
+    for (0..3) |i| {
+        const w = committer.reserve(1).?;
+        w.slice[0] = i;
+        committer.commit(1);
     }
+    try std.testing.expectEqual(@as(usize, 3), committer.pending);
+    try std.testing.expectEqual(@as(usize, 0), ring.len());
+
+    // The fourth crosses the threshold and auto-flushes
+    const w = committer.reserve(1).?;
+    w.slice[0] = 3;
+    committer.commit(1);
+    try std.testing.expectEqual(@as(usize, 0), committer.pending);
+
+    var out: [8]u64 = undefined;
+    try std.testing.expectEqual(@as(usize, 4), ring.recv(&out));
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 0, 1, 2, 3 }, out[0..4]);
+
+    // Explicit flush publishes a sub-threshold remainder
+    const w2 = committer.reserve(1).?;
+    w2.slice[0] = 9;
+    committer.commit(1);
+    try std.testing.expectEqual(@as(usize, 0), ring.len());
+    committer.flush();
+    try std.testing.expectEqual(@as(usize, 0), committer.pending);
+    try std.testing.expectEqual(@as(usize, 1), ring.len());
+}
 
-    // Consume up to 5
-    var sum: u64 = 0;
+test "ring: autoCommitter close flushes the final partial batch" {
+    var ring = Ring(u64, Config{ .ring_bits = 4 }){};
+    var committer = ring.autoCommitter(8);
+
+    // Three items: below the threshold, so nothing published yet
+    for (0..3) |i| {
+        const r = committer.reserve(1).?;
+        r.slice[0] = i;
+        committer.commit(1);
+    }
+    try std.testing.expectEqual(@as(usize, 3), committer.pending);
+    try std.testing.expectEqual(@as(usize, 0), ring.len());
+
+    // Closing through the committer publishes them before the flag
+    committer.close();
+    try std.testing.expectEqual(@as(usize, 0), committer.pending);
+    try std.testing.expect(ring.isClosed());
+
+    var count: u64 = 0;
     const Handler = struct {
-        sum: *u64,
+        count: *u64,
         pub fn process(self: @This(), item: *const u64) void {
-            self.sum.* += item.*;
+            _ = item;
+            self.count.* += 1;
         }
     };
-    const consumed = ring.consumeUpTo(5, Handler{ .sum = &sum });
+    try std.testing.expectEqual(@as(usize, 3), ring.consumeUntilClosed(Handler{ .count = &count }));
+}
 
-    try std.testing.expectEqual(@as(usize, 5), consumed);
-    try std.testing.expectEqual(@as(u64, 0 + 10 + 20 + 30 + 40), sum);
-    try std.testing.expectEqual(@as(usize, 5), ring.len()); // 5 left
+test "ring: reserveBoth hands out both sides of the wrap" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    _ = ring.send(&[_]u64{ 0, 0, 0, 0, 0, 0 });
+    ring.advance(6);
+
+    // 5 slots spanning the boundary: 2 pre-wrap, 3 post-wrap
+    const segs = ring.reserveBoth(5).?;
+    try std.testing.expectEqual(@as(usize, 2), segs.first.len);
+    try std.testing.expectEqual(@as(usize, 3), segs.second.len);
+    for (segs.first, 1..) |*slot, v| slot.* = v;
+    for (segs.second, 3..) |*slot, v| slot.* = v;
+    ring.commit(5);
+
+    var out: [8]u64 = undefined;
+    var got: usize = 0;
+    while (got < 5) got += ring.recv(out[got..]);
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 1, 2, 3, 4, 5 }, out[0..5]);
+
+    // More than the free space is refused outright
+    _ = ring.send(&[_]u64{ 0, 0, 0, 0, 0, 0 });
+    try std.testing.expect(ring.reserveBoth(3) == null);
+}
+
+test "ring: reservation reports a clipped grant" {
+    var ring = Ring(u64, Config{ .ring_bits = 3 }){}; // 8 slots
+
+    // Park the cursors near the end so a big request clips at the wrap
+    _ = ring.send(&[_]u64{ 0, 0, 0, 0, 0, 0 });
+    ring.advance(6);
+
+    const r = ring.reserve(4).?;
+    try std.testing.expectEqual(@as(usize, 4), r.requested);
+    try std.testing.expectEqual(@as(usize, 2), r.slice.len); // clipped
+    try std.testing.expect(!r.isComplete());
+    r.slice[0] = 1;
+    r.slice[1] = 2;
+    ring.commit(r.slice.len); // the safe amount, not the requested 4
+
+    const r2 = ring.reserve(2).?;
+    try std.testing.expect(r2.isComplete());
+}
+
+test "ring: reserveChecked tells transient full from permanent failure" {
+    const R = Ring(u64, Config{ .ring_bits = 2 }); // 4 slots
+    var ring = R{};
+
+    // Oversized request is a programming error, not a full ring
+    try std.testing.expect(ring.reserveChecked(R.capacity() + 1) == .too_large);
+
+    switch (ring.reserveChecked(4)) {
+        .granted => |r| {
+            for (r.slice, 0..) |*slot, i| slot.* = i;
+            ring.commit(r.slice.len);
+        },
+        else => return error.TestUnexpectedResult,
+    }
+
+    // Full right now: retryable
+    try std.testing.expect(ring.reserveChecked(1) == .would_block);
+    ring.advance(4);
+    try std.testing.expect(ring.reserveChecked(1) == .granted);
+
+    // Closed: permanent, even though space is free
+    ring.commit(1);
+    ring.advance(1);
+    ring.close();
+    try std.testing.expect(ring.reserveChecked(1) == .closed);
+}
+
+test "ring: reserveAttempt retries with a cached tail" {
+    var ring = Ring(u64, Config{ .ring_bits = 2 }){}; // 4 slots
+
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4 }); // full
+
+    var attempt = ring.reserveAttempt(2);
+    try std.testing.expect(attempt.retry() == null);
+    try std.testing.expect(attempt.retry() == null);
+
+    // The consumer frees space; the next retry succeeds at the old tail
+    ring.advance(2);
+    const r = attempt.retry().?;
+    try std.testing.expectEqual(@as(usize, 2), r.slice.len);
+    r.slice[0] = 5;
+    r.slice[1] = 6;
+    ring.commit(2);
+    try std.testing.expect(ring.isFull());
+}
+
+test "ring: zero commit and advance touch nothing" {
+    var ring = Ring(u64, Config{ .ring_bits = 4, .enable_metrics = true }){};
+
+    _ = ring.reserve(2); // reserve-then-abort
+    ring.commit(0);
+    ring.advance(0);
+
+    const s = ring.snapshot();
+    try std.testing.expectEqual(@as(u64, 0), s.tail);
+    try std.testing.expectEqual(@as(u64, 0), s.head);
+
+    // Not even the batch counters moved
+    const m = ring.getMetrics();
+    try std.testing.expectEqual(@as(u64, 0), m.batches_sent);
+    try std.testing.expectEqual(@as(u64, 0), m.batches_received);
+}
+
+test "ring: tryCommit rejects over-commit" {
+    var ring = Ring(u64, Config{ .ring_bits = 2 }){}; // 4 slots
+
+    const w = ring.reserve(2).?;
+    w.slice[0] = 1;
+    w.slice[1] = 2;
+    try ring.tryCommit(2);
+
+    try std.testing.expectError(error.Overflow, ring.tryCommit(3)); // only 2 free
+    try std.testing.expectEqual(@as(usize, 2), ring.len());
+}
+
+test "ring: ring_bits == 0 works as a single-slot mailbox" {
+    var ring = Ring(u64, Config{ .ring_bits = 0 }){};
+    try std.testing.expectEqual(@as(usize, 1), @TypeOf(ring).capacity());
+
+    var out: [1]u64 = undefined;
+    for (0..100) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = i;
+        ring.commit(1);
+
+        // Exactly one item in flight: the next reserve must refuse
+        try std.testing.expect(ring.isFull());
+        try std.testing.expect(ring.reserve(1) == null);
+
+        try std.testing.expectEqual(@as(usize, 1), ring.recv(&out));
+        try std.testing.expectEqual(@as(u64, i), out[0]);
+        try std.testing.expect(ring.isEmpty());
+    }
+}
+
+test "ring: compact 32-bit cursors behave like the default" {
+    var ring = Ring(u16, Config{ .ring_bits = 3, .compact_cursors = true }){}; // 8 slots
+
+    // Cycle enough items to wrap the ring index several times
+    for (0..50) |i| {
+        const w = ring.reserve(1).?;
+        w.slice[0] = @intCast(i);
+        ring.commit(1);
+
+        var out: [1]u16 = undefined;
+        try std.testing.expectEqual(@as(usize, 1), ring.recv(&out));
+        try std.testing.expectEqual(@as(u16, @intCast(i)), out[0]);
+    }
 
-    // Consume remaining
-    sum = 0;
-    const consumed2 = ring.consumeUpTo(10, Handler{ .sum = &sum });
-    try std.testing.expectEqual(@as(usize, 5), consumed2);
-    try std.testing.expectEqual(@as(u64, 50 + 60 + 70 + 80 + 90), sum);
     try std.testing.expect(ring.isEmpty());
+    const s = ring.snapshot();
+    try std.testing.expectEqual(@as(u64, 50), s.tail);
+}
+
+test "ring: prefetch indices stay in bounds at cursor wraparound" {
+    var ring = Ring(u64, Config{ .ring_bits = 3, .compact_cursors = true }){};
+
+    // Park both cursors just below the u32 wrap so reserve/readable run
+    // their prefetches right where `cursor + n` overflows the width
+    const near: u32 = std.math.maxInt(u32) - 2;
+    ring.tail.store(near, .monotonic);
+    ring.head.store(near, .monotonic);
+    ring.cached_head = near;
+    ring.cached_tail = near;
+
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4, 5 });
+
+    var out: [8]u64 = undefined;
+    var got: usize = 0;
+    while (got < 5) got += ring.recv(out[got..]);
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 1, 2, 3, 4, 5 }, out[0..5]);
 }
 
 test "ring: backoff on full" {
@@ -584,12 +3425,280 @@ test "ring: backoff on full" {
     }
 
     // Should fail
+    try std.testing.expect(ring.isFull());
     try std.testing.expect(ring.reserve(1) == null);
 
     // Backoff should also fail (but gracefully)
     try std.testing.expect(ring.reserveWithBackoff(1) == null);
 }
 
+test "ring: reserve composes with a caller-supplied backoff" {
+    var ring = Ring(u64, Config{ .ring_bits = 2 }){}; // 4 slots
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4 }); // full
+
+    // Minimal custom policy: give up after three snoozes
+    const Impatient = struct {
+        snoozes: usize = 0,
+        pub fn snooze(self: *@This()) void {
+            self.snoozes += 1;
+        }
+        pub fn isCompleted(self: *const @This()) bool {
+            return self.snoozes >= 3;
+        }
+    };
+
+    var policy = Impatient{};
+    try std.testing.expect(ring.reserveWithBackoffUsing(1, &policy) == null);
+    try std.testing.expectEqual(@as(usize, 3), policy.snoozes);
+
+    ring.advance(1);
+    policy = Impatient{};
+    try std.testing.expect(ring.reserveWithBackoffUsing(1, &policy) != null);
+    try std.testing.expectEqual(@as(usize, 0), policy.snoozes);
+}
+
+test "ring: deinitItems releases unconsumed owning elements" {
+    const Owned = struct {
+        ptr: ?*u64,
+
+        var released: usize = 0;
+        fn deinitItem(item: *@This()) void {
+            if (item.ptr != null) released += 1;
+            item.ptr = null;
+        }
+    };
+
+    var ring = Ring(Owned, Config{ .ring_bits = 4 }){};
+    var payload: u64 = 42;
+
+    const w = ring.reserve(3).?;
+    for (w.slice) |*slot| slot.* = .{ .ptr = &payload };
+    ring.commit(3);
+
+    // Consume one, leave two live in the ring
+    ring.advance(1);
+
+    Owned.released = 0;
+    ring.deinitItems(Owned.deinitItem);
+    try std.testing.expectEqual(@as(usize, 2), Owned.released);
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "ring: snapshot is internally consistent" {
+    var ring = Ring(u64, default_config){};
+
+    _ = ring.send(&[_]u64{ 1, 2, 3 });
+    ring.advance(1);
+
+    const s = ring.snapshot();
+    try std.testing.expectEqual(@as(u64, 1), s.head);
+    try std.testing.expectEqual(@as(u64, 3), s.tail);
+    try std.testing.expectEqual(@as(usize, 2), s.len);
+    try std.testing.expect(!s.closed);
+}
+
+test "ring: byte-size guard admits large legal configurations" {
+    // 2^20 slots of 64-byte elements = 64 MiB, far under the isize cap;
+    // the negative case is a compile error by design, so only the
+    // accepting side is testable here
+    const Wide = struct { bytes: [64]u8 };
+    const R = Ring(Wide, Config{ .ring_bits = 20 });
+    try std.testing.expectEqual(@as(usize, 64 << 20), R.capacityBytes());
+}
+
+test "ring: assertFitsIn accepts a ring inside its byte budget" {
+    // 16 slots of u32 plus three 128-byte lines: comfortably L1-resident
+    const Small = Ring(u32, Config{ .ring_bits = 4 });
+    comptime Small.assertFitsIn(32 * 1024);
+    try std.testing.expect(@sizeOf(Small) <= 32 * 1024);
+}
+
+test "ring: unpadded layout shrinks a tiny ring and still round-trips" {
+    const Padded = Ring(u32, Config{ .ring_bits = 3 });
+    const Compact = Ring(u32, Config{ .ring_bits = 3, .pad_cache_lines = false });
+
+    // Three 128-byte lines of padding dwarf a 32-byte buffer; dropping
+    // them is the whole point of the flag
+    try std.testing.expect(@sizeOf(Compact) < @sizeOf(Padded));
+    try std.testing.expect(@alignOf(Compact) < 128);
+
+    var ring = Compact{};
+    _ = ring.send(&[_]u32{ 7, 8, 9 });
+    var buf: [8]u32 = undefined;
+    const n = ring.recv(&buf);
+    try std.testing.expectEqualSlices(u32, &[_]u32{ 7, 8, 9 }, buf[0..n]);
+}
+
+test "ring: memory footprint covers buffer and control lines" {
+    const R = Ring(u64, Config{ .ring_bits = 4 });
+    // At least the raw buffer, plus the three aligned control lines
+    try std.testing.expect(R.memoryFootprint() >= R.capacity() * @sizeOf(u64));
+
+    // The shm-sizing split: control block + data buffer <= total
+    try std.testing.expectEqual(@as(usize, 16 * 8), R.capacityBytes());
+    try std.testing.expect(R.headerBytes() > 0);
+    try std.testing.expect(R.headerBytes() + R.capacityBytes() <= R.memoryFootprint());
+
+    const C = Channel(u64, Config{ .ring_bits = 4, .max_producers = 4 });
+    try std.testing.expect(C.totalFootprint() >= 4 * R.memoryFootprint());
+}
+
+test "ring: exportHeader mirrors the snapshot with a fixed layout" {
+    var ring = Ring(u64, default_config){};
+
+    _ = ring.send(&[_]u64{ 1, 2, 3 });
+    ring.advance(1);
+    ring.close();
+
+    const h = ring.exportHeader();
+    try std.testing.expectEqual(@as(u64, 1), h.head);
+    try std.testing.expectEqual(@as(u64, 3), h.tail);
+    try std.testing.expectEqual(@as(u64, @TypeOf(ring).capacity()), h.capacity);
+    try std.testing.expectEqual(RingHeader.HEADER_VERSION, h.version);
+    try std.testing.expectEqual(@as(u32, 1), h.closed);
+
+    // The ABI contract: cursors live on separate 128-byte lines
+    try std.testing.expectEqual(@as(usize, 0), @offsetOf(RingHeader, "head"));
+    try std.testing.expectEqual(@as(usize, 128), @offsetOf(RingHeader, "tail"));
+}
+
+test "ring: clear resets a used ring for reuse" {
+    var ring = Ring(u64, Config{ .ring_bits = 2 }){}; // 4 slots
+
+    _ = ring.send(&[_]u64{ 1, 2, 3, 4 });
+    ring.advance(2);
+    ring.close();
+
+    ring.clear();
+    try std.testing.expect(ring.isEmpty());
+    try std.testing.expect(!ring.isClosed());
+
+    // Fully reusable, including a complete fill
+    try std.testing.expectEqual(@as(usize, 4), ring.send(&[_]u64{ 5, 6, 7, 8 }));
+    try std.testing.expectEqual(@as(u64, 5), ring.peekSlice()[0]);
+}
+
+test "ring: pushOverwrite keeps the latest N samples" {
+    var ring = Ring(u64, Config{ .ring_bits = 2, .enable_metrics = true }){}; // 4 slots
+
+    for (0..10) |i| ring.pushOverwrite(i);
+
+    // Only the four most recent survive, oldest first
+    var out: [4]u64 = undefined;
+    var got: usize = 0;
+    while (got < 4) got += ring.recv(out[got..]);
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 6, 7, 8, 9 }, &out);
+    try std.testing.expect(ring.isEmpty());
+
+    const m = ring.getMetrics();
+    try std.testing.expectEqual(@as(u64, 10), m.messages_sent);
+    try std.testing.expectEqual(@as(u64, 6), m.messages_dropped);
+}
+
+test "ring: sendWith full-ring policies" {
+    var ring = Ring(u64, Config{ .ring_bits = 2, .enable_metrics = true }){}; // 4 slots
+
+    for (0..4) |i| {
+        try std.testing.expectEqual(SendOutcome.sent, ring.sendWith(i, .fail));
+    }
+
+    // Full: fail gives up, drop_new counts the drop
+    try std.testing.expectEqual(SendOutcome.full, ring.sendWith(99, .fail));
+    try std.testing.expectEqual(SendOutcome.dropped, ring.sendWith(99, .drop_new));
+    try std.testing.expectEqual(@as(u64, 1), ring.getMetrics().messages_dropped);
+
+    // Block succeeds once the consumer frees a slot... or bails on close
+    ring.close();
+    try std.testing.expectEqual(SendOutcome.full, ring.sendWith(99, .block));
+}
+
+test "mpmc: single-threaded enqueue/dequeue across many laps" {
+    var q = MpmcRing(u64, Config{ .ring_bits = 2 }).init(); // 4 slots
+
+    var next: u64 = 0;
+    var expect: u64 = 0;
+    for (0..10) |_| {
+        while (q.enqueue(next)) next += 1; // fill to the brim
+        try std.testing.expect(!q.enqueue(next));
+        try std.testing.expectEqual(@as(usize, 4), q.len());
+
+        while (q.dequeue()) |v| {
+            try std.testing.expectEqual(expect, v);
+            expect += 1;
+        }
+        try std.testing.expectEqual(@as(?u64, null), q.dequeue());
+    }
+    try std.testing.expectEqual(next, expect);
+}
+
+test "mpmc: two producers and two consumers agree on the totals" {
+    const MSG: u64 = 50_000; // per producer
+    const Q = MpmcRing(u64, Config{ .ring_bits = 8 });
+
+    const q = try Q.create(std.testing.allocator);
+    defer q.destroy(std.testing.allocator);
+
+    var claimed = std.atomic.Value(u64).init(0);
+    var sum = std.atomic.Value(u64).init(0);
+
+    const producer = struct {
+        fn run(queue: *Q) void {
+            var i: u64 = 0;
+            while (i < MSG) {
+                if (queue.enqueue(i)) i += 1 else std.atomic.spinLoopHint();
+            }
+        }
+    };
+    const consumer = struct {
+        fn run(queue: *Q, claimed_: *std.atomic.Value(u64), sum_: *std.atomic.Value(u64)) void {
+            while (claimed_.load(.monotonic) < 2 * MSG) {
+                if (queue.dequeue()) |v| {
+                    _ = claimed_.fetchAdd(1, .monotonic);
+                    _ = sum_.fetchAdd(v, .monotonic);
+                } else {
+                    std.atomic.spinLoopHint();
+                }
+            }
+        }
+    };
+
+    var threads: [4]std.Thread = undefined;
+    threads[0] = try std.Thread.spawn(.{}, producer.run, .{q});
+    threads[1] = try std.Thread.spawn(.{}, producer.run, .{q});
+    threads[2] = try std.Thread.spawn(.{}, consumer.run, .{ q, &claimed, &sum });
+    threads[3] = try std.Thread.spawn(.{}, consumer.run, .{ q, &claimed, &sum });
+    for (&threads) |*t| t.join();
+
+    try std.testing.expectEqual(2 * MSG, claimed.load(.acquire));
+    try std.testing.expectEqual(2 * (MSG * (MSG - 1) / 2), sum.load(.acquire));
+}
+
+test "framed: length-prefixed frames round-trip across the wraparound" {
+    var framed = FramedRing(Config{ .ring_bits = 4 }){}; // 16 bytes
+
+    var out = std.ArrayList(u8).init(std.testing.allocator);
+    defer out.deinit();
+
+    // A frame that can never fit is refused outright
+    try std.testing.expect(!framed.writeFrame("way too long for this ring"));
+
+    // Several fill/drain rounds so later frames straddle the wrap
+    for (0..5) |i| {
+        var payload: [6]u8 = undefined;
+        for (&payload, 0..) |*b, j| b.* = @intCast(i * 10 + j);
+        try std.testing.expect(framed.writeFrame(&payload)); // 10 bytes
+        try std.testing.expect(framed.writeFrame(&[_]u8{@intCast(i)})); // 5 bytes
+        try std.testing.expect(!framed.writeFrame(&[_]u8{ 1, 2, 3 })); // 7 > 1 free
+
+        out.clearRetainingCapacity();
+        try std.testing.expectEqual(@as(?usize, 6), try framed.readFrame(&out));
+        try std.testing.expectEqual(@as(?usize, 1), try framed.readFrame(&out));
+        try std.testing.expectEqual(@as(?usize, null), try framed.readFrame(&out));
+        try std.testing.expectEqualSlices(u8, &payload, out.items[0..6]);
+        try std.testing.expectEqual(@as(u8, @intCast(i)), out.items[6]);
+    }
+}
+
 test "channel: multi-producer" {
     var ch = Channel(u64, default_config).init();
 
@@ -649,6 +3758,277 @@ test "channel: consumeAll up to limit" {
     try std.testing.expect(sum >= 10);
 }
 
+test "channel: drainAll flushes every ring with producer ids" {
+    var ch = Channel(u64, default_config).init();
+
+    const p0 = try ch.register();
+    const p1 = try ch.register();
+    _ = p0.send(&[_]u64{ 1, 2 });
+    _ = p1.send(&[_]u64{ 10, 20, 30 });
+    ch.close();
+
+    var sums = [_]u64{ 0, 0 };
+    const Handler = struct {
+        sums: *[2]u64,
+        pub fn process(self: @This(), id: usize, item: *const u64) void {
+            self.sums[id] += item.*;
+        }
+    };
+    try std.testing.expectEqual(@as(usize, 5), ch.drainAll(Handler{ .sums = &sums }));
+    try std.testing.expectEqual(@as(u64, 3), sums[0]);
+    try std.testing.expectEqual(@as(u64, 60), sums[1]);
+    try std.testing.expectEqual(@as(usize, 0), ch.drainAll(Handler{ .sums = &sums }));
+}
+
+test "channel: drainPrioritized services rings in the given order" {
+    var ch = Channel(u64, default_config).init();
+
+    const p0 = try ch.register();
+    const p1 = try ch.register();
+
+    _ = p0.send(&[_]u64{ 1, 2, 3 });
+    _ = p1.send(&[_]u64{ 10, 20, 30 });
+
+    var seen = std.BoundedArray(u64, 8).init(0) catch unreachable;
+    const Handler = struct {
+        seen: *std.BoundedArray(u64, 8),
+        pub fn process(self: @This(), item: *const u64) void {
+            self.seen.append(item.*) catch unreachable;
+        }
+    };
+    const h = Handler{ .seen = &seen };
+
+    // Ring 1 first, capped at 2 per ring; unknown ids are skipped
+    const order = [_]usize{ 1, 0, 7 };
+    try std.testing.expectEqual(@as(usize, 4), ch.drainPrioritized(&order, 2, h));
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 10, 20, 1, 2 }, seen.constSlice());
+
+    // Next sweep picks up the remainders in the same priority order
+    try std.testing.expectEqual(@as(usize, 2), ch.drainPrioritized(&order, 2, h));
+    try std.testing.expectEqualSlices(u64, &[_]u64{ 10, 20, 1, 2, 30, 3 }, seen.constSlice());
+}
+
+test "ring: FIFO across threads with many wraparounds" {
+    // Small ring forces thousands of wraparounds; the consumer asserts the
+    // exact sequence 0..N with no gaps, duplicates, or reorderings. This is
+    // the core correctness property everything else depends on.
+    const N: u64 = 1_000_000;
+    const TestRing = Ring(u64, Config{ .ring_bits = 8 }); // 256 slots
+
+    const Ctx = struct {
+        fn produce(ring: *TestRing) void {
+            var seq: u64 = 0;
+            while (seq < N) {
+                if (ring.reserve(1)) |r| {
+                    r.slice[0] = seq;
+                    ring.commit(1);
+                    seq += 1;
+                } else std.atomic.spinLoopHint();
+            }
+        }
+    };
+
+    var gpa = std.heap.GeneralPurposeAllocator(.{}){};
+    defer _ = gpa.deinit();
+    const ring = try TestRing.create(gpa.allocator());
+    defer ring.destroy(gpa.allocator());
+
+    const t = try std.Thread.spawn(.{}, Ctx.produce, .{ring});
+
+    var expected: u64 = 0;
+    while (expected < N) {
+        const slice = ring.readable() orelse {
+            std.atomic.spinLoopHint();
+            continue;
+        };
+        for (slice) |v| {
+            try std.testing.expectEqual(expected, v);
+            expected += 1;
+        }
+        ring.advance(slice.len);
+    }
+
+    t.join();
+    try std.testing.expect(ring.isEmpty());
+}
+
+test "channel: adjacent rings never share a cache line" {
+    // Rings are stored inline in one array, so the guard is structural:
+    // the ring size must be a multiple of its 128-byte alignment, which
+    // puts ring i's consumer line and ring i+1's producer line on
+    // different lines at any producer count.
+    const R = Ring(u64, default_config);
+    try std.testing.expect(@alignOf(R) >= 128);
+    try std.testing.expectEqual(@as(usize, 0), @sizeOf(R) % 128);
+
+    var ch = Channel(u64, Config{ .ring_bits = 4, .max_producers = 4 }){};
+    const a = @intFromPtr(&ch.rings[0].head);
+    const b = @intFromPtr(&ch.rings[1].tail);
+    try std.testing.expect(b / 128 != a / 128);
+}
+
+test "channel: producer id pairs with getRing" {
+    var ch = Channel(u64, default_config){};
+
+    const a = try ch.register();
+    const b = try ch.register();
+    try std.testing.expectEqual(@as(usize, 0), a.id);
+    try std.testing.expectEqual(@as(usize, 1), b.id);
+    try std.testing.expectEqual(a.ring, ch.getRing(a.id));
+    try std.testing.expectEqual(b.ring, ch.getRing(b.id));
+}
+
+test "channel: producer count accessors" {
+    var ch = Channel(u64, default_config){};
+    try std.testing.expectEqual(@as(usize, 0), ch.producerCount());
+    try std.testing.expectEqual(@as(usize, 0), ch.activeProducers());
+
+    const a = try ch.register();
+    _ = try ch.register();
+    try std.testing.expectEqual(@as(usize, 2), ch.producerCount());
+    try std.testing.expectEqual(@as(usize, 2), ch.activeProducers());
+
+    // A producer retiring drops the live count but not the total
+    a.ring.active.store(false, .release);
+    try std.testing.expectEqual(@as(usize, 2), ch.producerCount());
+    try std.testing.expectEqual(@as(usize, 1), ch.activeProducers());
+}
+
+test "channel: splitAll hands out one end pair per ring" {
+    var ch = Channel(u64, Config{ .ring_bits = 4, .max_producers = 3 }){};
+    const ends = ch.splitAll();
+
+    // Every slot is claimed up front; the sweep sees all rings
+    try std.testing.expectEqual(@as(usize, 3), ch.producerCount());
+    try std.testing.expectEqual(@as(usize, 3), ch.activeProducers());
+
+    // Traffic through pair 1 stays in ring 1
+    _ = ends.producers[1].send(&[_]u64{ 10, 11 });
+    var buf: [4]u64 = undefined;
+    try std.testing.expectEqual(@as(usize, 0), ends.consumers[0].recv(&buf));
+    try std.testing.expectEqual(@as(usize, 2), ends.consumers[1].recv(&buf));
+    try std.testing.expectEqual(@as(u64, 10), buf[0]);
+
+    // A producer end retiring is visible only to its own consumer end
+    ends.producers[0].close();
+    try std.testing.expect(ends.consumers[0].isClosed());
+    try std.testing.expect(!ends.consumers[2].isClosed());
+}
+
+test "channel: awaitProducers covers the registration race" {
+    const Ch = Channel(u64, Config{ .ring_bits = 4, .max_producers = 4 });
+    const ch = try Ch.create(std.testing.allocator);
+    defer ch.destroy(std.testing.allocator);
+
+    // Already satisfied: returns without waiting
+    try std.testing.expect(ch.awaitProducers(0, 0));
+
+    const late = struct {
+        fn run(c: *Ch) void {
+            // A little startup work before registering
+            const t0 = std.time.Instant.now() catch unreachable;
+            while ((std.time.Instant.now() catch unreachable).since(t0) < 100_000) {
+                std.atomic.spinLoopHint();
+            }
+            _ = c.register() catch {};
+        }
+    };
+    var threads: [3]std.Thread = undefined;
+    for (&threads) |*t| t.* = try std.Thread.spawn(.{}, late.run, .{ch});
+    defer for (&threads) |*t| t.join();
+
+    try std.testing.expect(ch.awaitProducers(3, std.time.ns_per_s));
+    try std.testing.expectEqual(@as(usize, 3), ch.producerCount());
+
+    // A fourth producer never arrives: the timeout fires and reports it
+    try std.testing.expect(!ch.awaitProducers(4, 100_000));
+}
+
+test "channel: registerOn records an advisory core hint" {
+    var ch = Channel(u64, default_config){};
+
+    const a = try ch.registerOn(3);
+    const b = try ch.register();
+
+    try std.testing.expectEqual(@as(?usize, 3), ch.producerCore(a.id));
+    try std.testing.expectEqual(@as(?usize, null), ch.producerCore(b.id));
+}
+
+test "channel: registerNamed attaches a diagnostic label" {
+    var ch = Channel(u64, default_config){};
+
+    const orders = try ch.registerNamed("orders");
+    const anon = try ch.register();
+
+    try std.testing.expectEqualStrings("orders", ch.producerName(orders.id).?);
+    try std.testing.expectEqual(@as(?[]const u8, null), ch.producerName(anon.id));
+}
+
+test "channel: register after close is refused" {
+    var ch = Channel(u64, default_config){};
+
+    const p = try ch.register();
+    _ = p;
+    ch.close();
+
+    try std.testing.expectError(error.Closed, ch.register());
+}
+
+test "channel: forEachRing visits registered rings with ids" {
+    var ch = Channel(u64, default_config).init();
+
+    const p1 = try ch.register();
+    const p2 = try ch.register();
+    _ = p1.send(&[_]u64{1});
+    _ = p2.send(&[_]u64{ 2, 3 });
+
+    const Visitor = struct {
+        visited: *usize,
+        backlog: *usize,
+        pub fn visit(self: @This(), id: usize, ring: anytype) void {
+            _ = id;
+            self.visited.* += 1;
+            self.backlog.* += ring.len();
+        }
+    };
+    var visited: usize = 0;
+    var backlog: usize = 0;
+    ch.forEachRing(Visitor{ .visited = &visited, .backlog = &backlog });
+
+    try std.testing.expectEqual(@as(usize, 2), visited);
+    try std.testing.expectEqual(@as(usize, 3), backlog);
+}
+
+test "publish/observe round-trip a guarded write" {
+    var payload: u64 = 0;
+    var flag = std.atomic.Value(u32).init(0);
+
+    payload = 42;
+    publish(u32, &flag, 1);
+
+    if (observe(u32, &flag) == 1) {
+        try std.testing.expectEqual(@as(u64, 42), payload);
+    } else {
+        return error.TestUnexpectedResult;
+    }
+}
+
+test "tsc: counter advances and converts to nanoseconds" {
+    const c0 = rdtsc();
+    var spin: usize = 0;
+    while (spin < 1000) : (spin += 1) std.atomic.spinLoopHint();
+    const c1 = rdtscp();
+    try std.testing.expect(c1 -% c0 > 0);
+
+    // 3G cycles at 3 GHz is exactly one second
+    try std.testing.expectEqual(@as(u64, std.time.ns_per_s), cyclesToNanos(3_000_000_000, 3_000_000_000));
+    try std.testing.expectEqual(@as(u64, 0), cyclesToNanos(0, 1));
+
+    // A coarse calibration lands in a plausible clock range (100 MHz–10 GHz)
+    const hz = calibrateTscHz(2 * std.time.ns_per_ms);
+    try std.testing.expect(hz > 100_000_000 and hz < 10_000_000_000);
+}
+
 test "backoff: spin progression" {
     var b = Backoff{};
 